@@ -0,0 +1,178 @@
+use embassy_time::{Duration, Timer};
+use rmk::channel::KEYBOARD_REPORT_CHANNEL;
+use rmk::event::{Axis, AxisEvent, AxisValType, Event};
+use rmk::hid::Report;
+use rmk::input_device::InputDevice;
+use usbd_hid::descriptor::MouseReport;
+
+/// A single ADC channel read, abstracted the way `Pmw3610Transport` abstracts
+/// the sensor's byte link: a board wires whichever HAL ADC driver it has to
+/// this trait instead of `AnalogJoystickDevice` depending on one directly.
+pub trait AnalogChannel {
+    async fn read(&mut self) -> u16;
+}
+
+/// Per-axis calibration in raw ADC counts.
+#[derive(Clone, Copy)]
+pub struct AxisCalibration {
+    pub min: u16,
+    pub center: u16,
+    pub max: u16,
+    /// Counts of travel around `center` treated as no input.
+    pub deadzone: u16,
+    pub invert: bool,
+}
+
+/// Whether calibrated displacement is reported as an absolute stick position
+/// or as cursor-style relative motion scaled by how far the stick is pushed.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum JoystickMode {
+    Absolute,
+    /// Counts emitted per poll at full deflection; smaller pushes scale down.
+    Relative { max_counts_per_poll: i16 },
+}
+
+pub struct AnalogJoystickConfig {
+    pub x: AxisCalibration,
+    pub y: AxisCalibration,
+    pub swap_xy: bool,
+    pub mode: JoystickMode,
+    pub poll_interval: Duration,
+}
+
+/// Two-ADC-channel joystick as an `InputDevice`, producing the same
+/// `Event::Joystick([AxisEvent; 3])` shape the PMW3610 path returns so both
+/// share one downstream processor chain.
+pub struct AnalogJoystickDevice<X, Y> {
+    chan_x: X,
+    chan_y: Y,
+    config: AnalogJoystickConfig,
+    /// Last position reported in `Absolute` mode, used to derive the delta
+    /// the HID mouse report needs (a USB mouse reports motion, not
+    /// position).
+    last_absolute: (i16, i16),
+}
+
+impl<X, Y> AnalogJoystickDevice<X, Y>
+where
+    X: AnalogChannel,
+    Y: AnalogChannel,
+{
+    pub fn new(chan_x: X, chan_y: Y, config: AnalogJoystickConfig) -> Self {
+        Self {
+            chan_x,
+            chan_y,
+            config,
+            last_absolute: (0, 0),
+        }
+    }
+
+    /// Map a raw ADC count to a signed displacement in `-127..=127`, applying
+    /// deadzone, per-side span scaling (min/center and center/max may differ)
+    /// and inversion.
+    fn calibrate(calib: &AxisCalibration, raw: u16) -> i16 {
+        let delta = raw as i32 - calib.center as i32;
+        if delta.unsigned_abs() <= calib.deadzone as u32 {
+            return 0;
+        }
+
+        let span = if delta > 0 {
+            (calib.max as i32 - calib.center as i32).max(1)
+        } else {
+            (calib.center as i32 - calib.min as i32).max(1)
+        };
+        let scaled = (delta * 127 / span).clamp(-127, 127) as i16;
+        if calib.invert {
+            -scaled
+        } else {
+            scaled
+        }
+    }
+}
+
+impl<X, Y> InputDevice for AnalogJoystickDevice<X, Y>
+where
+    X: AnalogChannel,
+    Y: AnalogChannel,
+{
+    async fn read_event(&mut self) -> Event {
+        loop {
+            Timer::after(self.config.poll_interval).await;
+
+            let raw_x = self.chan_x.read().await;
+            let raw_y = self.chan_y.read().await;
+
+            let x = Self::calibrate(&self.config.x, raw_x);
+            let y = Self::calibrate(&self.config.y, raw_y);
+            let (x, y) = if self.config.swap_xy { (y, x) } else { (x, y) };
+
+            // Only `Relative` mode treats `(0, 0)` as "nothing to report" —
+            // in `Absolute` mode it's the stick's centered position, a real
+            // reading that must keep reaching the host (e.g. to recenter a
+            // cursor after the stick is released).
+            if matches!(self.config.mode, JoystickMode::Relative { .. }) && x == 0 && y == 0 {
+                continue;
+            }
+
+            let (typ, report_x, report_y) = match self.config.mode {
+                JoystickMode::Absolute => {
+                    // USB mice report motion, not position, so the HID path
+                    // sends the delta from the last reported position; the
+                    // `Event::Joystick` returned below still carries the
+                    // true absolute reading for any other consumer.
+                    let (last_x, last_y) = self.last_absolute;
+                    self.last_absolute = (x, y);
+                    let delta_x = (x as i32 - last_x as i32).clamp(-127, 127) as i8;
+                    let delta_y = (y as i32 - last_y as i32).clamp(-127, 127) as i8;
+
+                    KEYBOARD_REPORT_CHANNEL
+                        .send(Report::MouseReport(MouseReport {
+                            buttons: 0,
+                            x: delta_x,
+                            y: delta_y,
+                            wheel: 0,
+                            pan: 0,
+                        }))
+                        .await;
+
+                    (AxisValType::Abs, x, y)
+                }
+                JoystickMode::Relative { max_counts_per_poll } => {
+                    let scale = |value: i16| -> i16 { (value as i32 * max_counts_per_poll as i32 / 127) as i16 };
+                    let rel_x = scale(x);
+                    let rel_y = scale(y);
+
+                    KEYBOARD_REPORT_CHANNEL
+                        .send(Report::MouseReport(MouseReport {
+                            buttons: 0,
+                            x: rel_x.clamp(-127, 127) as i8,
+                            y: rel_y.clamp(-127, 127) as i8,
+                            wheel: 0,
+                            pan: 0,
+                        }))
+                        .await;
+
+                    (AxisValType::Rel, rel_x, rel_y)
+                }
+            };
+
+            return Event::Joystick([
+                AxisEvent {
+                    typ,
+                    axis: Axis::X,
+                    value: report_x,
+                },
+                AxisEvent {
+                    typ,
+                    axis: Axis::Y,
+                    value: report_y,
+                },
+                AxisEvent {
+                    typ,
+                    axis: Axis::Z,
+                    value: 0,
+                },
+            ]);
+        }
+    }
+}
@@ -1,8 +1,10 @@
 #![no_main]
 #![no_std]
 
+use defmt::unwrap;
+use embassy_time::Duration;
 use rmk::macros::rmk_central;
-use roba_rmk::{BleConnectionLed};
+use roba_rmk::{run_status_leds, stash_ble_status, BleConnectionLed, StatusLed, StatusLeds};
 use embassy_nrf::gpio::{Output, Level, OutputDrive};
 
 #[rmk_central]
@@ -11,6 +13,11 @@ mod keyboard_central {
     fn ble_connection_led() -> BleConnectionLed {
         let led_blue = Output::new(p.P0_06, Level::High, OutputDrive::Standard);
         let led_red = Output::new(p.P0_26, Level::High, OutputDrive::Standard);
-        BleConnectionLed::new(led_blue, led_red)
+        let status_leds = StatusLeds::new([StatusLed::new(led_blue, true), StatusLed::new(led_red, true)]);
+        unwrap!(spawner.spawn(run_status_leds(status_leds, Duration::from_millis(20), Some((0, 1)))));
+
+        let mut ble_led = BleConnectionLed::new(20, Duration::from_secs(5));
+        ble_led.set_status_sink(stash_ble_status);
+        ble_led
     }
 }
@@ -1,11 +1,30 @@
 use rmk::types::action::{EncoderAction, KeyAction};
 use rmk::{a, encoder, k, lt};
+use smart_leds::RGB8;
+
+use crate::layer_led::LayerStyle;
+use crate::midi::MidiEncoderAction;
+use crate::midi_cc;
 
 pub(crate) const COL: usize = 11;
 pub(crate) const ROW: usize = 4;
 pub(crate) const NUM_LAYER: usize = 8;
 pub(crate) const NUM_ENCODER: usize = 1;
 
+/// Per-layer LED indication, in the same layer order as [`get_default_keymap`].
+/// A board customizes layer colors here the same way it customizes keys.
+#[rustfmt::skip]
+pub const LAYER_STYLES: [LayerStyle; NUM_LAYER] = [
+    LayerStyle::Color(RGB8::new(0, 0, 32)),    // Layer 0 - Default
+    LayerStyle::Color(RGB8::new(32, 0, 0)),    // Layer 1
+    LayerStyle::Color(RGB8::new(0, 32, 0)),    // Layer 2
+    LayerStyle::Color(RGB8::new(32, 32, 0)),   // Layer 3
+    LayerStyle::Color(RGB8::new(0, 32, 32)),   // Layer 4
+    LayerStyle::Color(RGB8::new(32, 0, 32)),   // Layer 5
+    LayerStyle::BlinkBlue(2),                  // Layer 6
+    LayerStyle::BlinkRed(3),                   // Layer 7 - Configuration
+];
+
 #[rustfmt::skip]
 pub const fn get_default_keymap() -> [[[KeyAction; COL]; ROW]; NUM_LAYER] {
     [
@@ -80,3 +99,21 @@ pub const fn get_default_encoder_map() -> [[EncoderAction; NUM_ENCODER]; NUM_LAY
         [encoder!(k!(KbVolumeUp), k!(KbVolumeDown))],
     ]
 }
+
+/// Per-layer MIDI override for the encoder: when `Some`, the encoder emits a
+/// relative Control Change instead of the `KbVolumeUp`/`KbVolumeDown` HID
+/// usages above. Layer 1 here is a MIDI-CC layer as an example; boards pick
+/// whichever layers suit their use.
+#[rustfmt::skip]
+pub const fn get_default_midi_encoder_overrides() -> [[Option<[MidiEncoderAction; 2]>; NUM_ENCODER]; NUM_LAYER] {
+    [
+        [None],
+        [Some([midi_cc!(0, 1, 1), midi_cc!(0, 1, -1)])],
+        [None],
+        [None],
+        [None],
+        [None],
+        [None],
+        [None],
+    ]
+}
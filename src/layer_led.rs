@@ -0,0 +1,164 @@
+use defmt::unwrap;
+use embassy_futures::select::{select, Either};
+use embassy_nrf::gpio::{Level, Output};
+use embassy_time::{Duration, Timer};
+use rmk::channel::{ControllerSub, CONTROLLER_CHANNEL};
+use rmk::controller::Controller;
+use rmk::event::ControllerEvent;
+use smart_leds::RGB8;
+
+use crate::keymap::NUM_LAYER;
+
+/// How a layer is surfaced to the user: either a framebuffer color handed to
+/// the underglow subsystem, or a blink count on the plain blue/red LEDs.
+#[derive(Clone, Copy)]
+pub enum LayerStyle {
+    Color(RGB8),
+    BlinkBlue(u8),
+    BlinkRed(u8),
+}
+
+#[derive(Clone, Copy)]
+enum LedChannel {
+    Blue,
+    Red,
+}
+
+/// In-flight blink-count job, advanced one 100ms phase at a time by
+/// `next_message`'s timer race rather than by sleeping inside
+/// `process_event`: `on` is the level the LED was just driven to, and
+/// `remaining_toggles` counts the transitions still left before the job
+/// completes.
+struct BlinkJob {
+    led: LedChannel,
+    on: bool,
+    remaining_toggles: u8,
+}
+
+/// Drives a per-layer indication, either by color (when paired with
+/// [`crate::rgb::RgbController`]) or by a blink count on the existing
+/// blue/red LEDs, so momentary (`lt!`) and toggled layers get QMK-style
+/// visual feedback.
+pub struct LayerIndicator {
+    led_blue: Output<'static>,
+    led_red: Output<'static>,
+    styles: [LayerStyle; NUM_LAYER],
+    current_layer: u8,
+    sub: ControllerSub,
+    /// Blink job in progress, if any. Kept as state here (rather than slept
+    /// through inside `process_event`) so a rapid subsequent layer change
+    /// preempts it instead of queuing behind it.
+    pending: Option<BlinkJob>,
+}
+
+impl LayerIndicator {
+    const PHASE_MS: u64 = 100;
+
+    pub fn new(
+        led_blue: Output<'static>,
+        led_red: Output<'static>,
+        styles: [LayerStyle; NUM_LAYER],
+    ) -> Self {
+        Self {
+            led_blue,
+            led_red,
+            styles,
+            current_layer: 0,
+            sub: unwrap!(CONTROLLER_CHANNEL.subscriber()),
+            pending: None,
+        }
+    }
+
+    fn led_mut(&mut self, led: LedChannel) -> &mut Output<'static> {
+        match led {
+            LedChannel::Blue => &mut self.led_blue,
+            LedChannel::Red => &mut self.led_red,
+        }
+    }
+
+    fn apply(&mut self, layer: u8) {
+        let Some(style) = self.styles.get(layer as usize).copied() else {
+            return;
+        };
+        match style {
+            // Color styles are driven by `RgbController`, which subscribes
+            // to the same `ControllerEvent::Layer` event against its own
+            // copy of this layer/style table. Still clear any blink job
+            // left running from a previous `BlinkBlue`/`BlinkRed` layer and
+            // turn both LEDs off, or a stale blink would keep going for up
+            // to `2 * count * PHASE_MS` on a layer that should show nothing
+            // on these two LEDs.
+            LayerStyle::Color(_) => {
+                self.pending = None;
+                self.led_blue.set_level(Level::High);
+                self.led_red.set_level(Level::High);
+            }
+            LayerStyle::BlinkBlue(count) => self.start_blink(LedChannel::Blue, count),
+            LayerStyle::BlinkRed(count) => self.start_blink(LedChannel::Red, count),
+        }
+    }
+
+    /// Starts (replacing any job already in progress) a blink-count job:
+    /// `count` on/off cycles at `PHASE_MS`, the same cadence the old
+    /// blocking loop used.
+    fn start_blink(&mut self, led: LedChannel, count: u8) {
+        if count == 0 {
+            return;
+        }
+        self.led_mut(led).set_level(Level::Low);
+        self.pending = Some(BlinkJob {
+            led,
+            on: true,
+            remaining_toggles: 2 * count - 1,
+        });
+    }
+
+    /// Advances the in-flight blink job by one phase.
+    fn step_blink(&mut self) {
+        let Some(job) = &mut self.pending else {
+            return;
+        };
+        if job.remaining_toggles == 0 {
+            self.pending = None;
+            return;
+        }
+        job.on = !job.on;
+        job.remaining_toggles -= 1;
+        let level = if job.on { Level::Low } else { Level::High };
+        self.led_mut(job.led).set_level(level);
+    }
+}
+
+impl Controller for LayerIndicator {
+    type Event = ControllerEvent;
+
+    async fn process_event(&mut self, event: Self::Event) {
+        if let ControllerEvent::Layer(layer) = event {
+            if layer != self.current_layer {
+                self.current_layer = layer;
+                self.apply(layer);
+            }
+        }
+    }
+
+    /// Same non-blocking pattern as `SplitConnectionLed`: the in-flight blink job's timer races the event channel so a rapid
+    /// subsequent layer change preempts it instead of queuing behind a
+    /// stale blink.
+    async fn next_message(&mut self) -> Self::Event {
+        loop {
+            if self.pending.is_some() {
+                match select(
+                    self.sub.next_message_pure(),
+                    Timer::after(Duration::from_millis(Self::PHASE_MS)),
+                )
+                .await
+                {
+                    Either::First(event) => return event,
+                    Either::Second(_) => self.step_blink(),
+                }
+            } else {
+                return self.sub.next_message_pure().await;
+            }
+        }
+    }
+}
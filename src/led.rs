@@ -1,50 +1,914 @@
+use core::cell::Cell;
+
 use defmt::{info, unwrap};
-use rmk::controller::Controller;
+use embassy_futures::select::{select, Either};
+use embassy_nrf::gpio::{Level, Output};
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::blocking_mutex::Mutex;
+use embassy_sync::channel::Channel;
+use embassy_time::{Duration, Timer};
+use embedded_hal::digital::OutputPin;
+use rmk::ble::BleState;
 use rmk::channel::{ControllerSub, CONTROLLER_CHANNEL};
+use rmk::controller::Controller;
 use rmk::event::ControllerEvent;
-use rmk::ble::BleState;
-use embassy_nrf::gpio::{Output, Level};
-use embassy_time::Timer;
 
-pub struct BleConnectionLed {
+/// Which physical LED a blink job is driving.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum LedChannel {
+    Blue,
+    Red,
+}
+
+/// BLE lifecycle state driving which pattern [`run_ble_connection_led`]
+/// renders. Distinct from `rmk::ble::BleState`: this also distinguishes a
+/// profile switch and a dropped connection, which need their own patterns.
+///
+/// Also carries the battery-driven states `BleConnectionLed` arbitrates
+/// against the BLE ones on the same two LEDs, since both ultimately funnel
+/// through [`BLE_LED_CHANNEL`] to the one render task.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum BleLedState {
+    /// Never connected or advertised yet: both LEDs off.
+    Idle,
+    /// Advertising / disconnected with no prior connection on this profile.
+    /// Carries the profile index so the steady pattern can be prefixed with
+    /// a `profile + 1`-count blink, the same encoding chunk0-2 established.
+    Advertising(u8),
+    /// Switched to a different profile while already connected.
+    ProfileSwitch,
+    /// Newly connected on the current profile. Carries the profile index
+    /// for the same blink-count prefix as `Advertising`.
+    Connected(u8),
+    /// Lost a connection that was previously established.
+    Dropped,
+    /// Charging, below the full-charge threshold: steady blue.
+    Charging,
+    /// Charging and at or above the full-charge threshold: a brief
+    /// blue/red alternation standing in for a green flash.
+    FullCharge,
+    /// Not charging and below the configured low-battery threshold: a
+    /// periodic red pulse, repeating every carried interval in
+    /// milliseconds. Carried rather than fixed so `BleConnectionLed::new`
+    /// can expose it as a constructor parameter.
+    LowBattery(u64),
+}
+
+/// One step of a rendered pattern: drive the LEDs to the given brightness
+/// (0 = off, 255 = full) for `duration_ms`, then advance to the next step.
+/// A plain GPIO backend treats any nonzero level as fully on; a PWM backend
+/// maps it straight to duty cycle.
+#[derive(Clone, Copy)]
+struct PatternStep {
+    blue_level: u8,
+    red_level: u8,
+    duration_ms: u64,
+}
+
+const fn step(blue_level: u8, red_level: u8, duration_ms: u64) -> PatternStep {
+    PatternStep {
+        blue_level,
+        red_level,
+        duration_ms,
+    }
+}
+
+/// Gamma-corrected triangle wave used to breathe a LED in and out smoothly
+/// rather than snapping between off and full brightness; 64 steps advanced
+/// one per tick by [`render_ble_led`].
+#[rustfmt::skip]
+const GAMMA_TABLE: [u8; 64] = [
+      0,   0,   1,   1,   3,   4,   6,   9,  12,  16,  20,  24,  29,  35,  41,  48,
+     55,  63,  72,  81,  91, 101, 112, 123, 135, 148, 161, 175, 190, 205, 221, 238,
+    255, 238, 221, 205, 190, 175, 161, 148, 135, 123, 112, 101,  91,  81,  72,  63,
+     55,  48,  41,  35,  29,  24,  20,  16,  12,   9,   6,   4,   3,   1,   1,   0,
+];
+
+/// A lifecycle state's rendered pattern: either a fixed step sequence (looped
+/// for a repeating cue, one-shot for a transient event that then leaves both
+/// LEDs off), or a continuous breathing effect on one LED.
+#[derive(Clone, Copy)]
+enum Pattern {
+    Steps {
+        steps: &'static [PatternStep],
+        looped: bool,
+    },
+    Breathing {
+        channel: LedChannel,
+        /// Full in-and-out cycle time; the 64-entry gamma table is spread
+        /// evenly across it.
+        period_ms: u64,
+    },
+}
+
+const OFF_PATTERN: Pattern = Pattern::Steps {
+    steps: &[step(0, 0, 1000)],
+    looped: true,
+};
+
+/// Slow deep blue breathing while advertising.
+const ADVERTISING_PATTERN: Pattern = Pattern::Breathing {
+    channel: LedChannel::Blue,
+    period_ms: 3000,
+};
+
+/// Short double-blink on profile switch.
+const PROFILE_SWITCH_PATTERN: Pattern = Pattern::Steps {
+    steps: &[
+        step(255, 0, 120),
+        step(0, 0, 120),
+        step(255, 0, 120),
+        step(0, 0, 120),
+    ],
+    looped: false,
+};
+
+/// Solid blue for ~2s to confirm the connection, then a low glow for 60s;
+/// the pattern loops, so the 2s solid blue recurs after each glow.
+const CONNECTED_PATTERN: Pattern = Pattern::Steps {
+    steps: &[step(255, 0, 2000), step(40, 0, 60_000)],
+    looped: true,
+};
+
+/// Fast red flash when a connection drops or pairing fails.
+const DROPPED_PATTERN: Pattern = Pattern::Steps {
+    steps: &[
+        step(0, 255, 80),
+        step(0, 0, 80),
+        step(0, 255, 80),
+        step(0, 0, 80),
+        step(0, 255, 80),
+        step(0, 0, 80),
+    ],
+    looped: false,
+};
+
+/// Steady blue while charging but not yet full.
+const CHARGING_PATTERN: Pattern = Pattern::Steps {
+    steps: &[step(180, 0, 60_000)],
+    looped: true,
+};
+
+/// Brief green-equivalent flash at full charge: fast blue/red alternation,
+/// since these two LEDs can't mix an actual green.
+const FULL_CHARGE_PATTERN: Pattern = Pattern::Steps {
+    steps: &[
+        step(255, 0, 60),
+        step(0, 255, 60),
+        step(255, 0, 60),
+        step(0, 255, 60),
+    ],
+    looped: false,
+};
+
+fn pattern_for(state: BleLedState) -> Pattern {
+    match state {
+        BleLedState::Idle => OFF_PATTERN,
+        // Rendered by `render_profile_count` then this pattern instead, so
+        // the profile-count blink prefix can run first; never actually
+        // reached with the profile index still attached.
+        BleLedState::Advertising(_) => ADVERTISING_PATTERN,
+        BleLedState::ProfileSwitch => PROFILE_SWITCH_PATTERN,
+        BleLedState::Connected(_) => CONNECTED_PATTERN,
+        BleLedState::Dropped => DROPPED_PATTERN,
+        BleLedState::Charging => CHARGING_PATTERN,
+        BleLedState::FullCharge => FULL_CHARGE_PATTERN,
+        // Rendered by `render_low_battery_pulse` instead, so the carried
+        // interval can drive the timing; never actually reached.
+        BleLedState::LowBattery(_) => OFF_PATTERN,
+    }
+}
+
+// ============================================================================
+// Composable status-indicator subsystem
+//
+// `render_ble_led` below is a dedicated pipeline for boards that want the
+// full brightness/breathing treatment on BLE status specifically. Boards
+// that don't need breathing, or that want BLE status to share physical LEDs
+// with other indicators (battery, caps/layer lock, charging), use the
+// simpler digital primitives below instead: `ble_blink_pattern_for` reduces
+// the same pattern table to on/off steps for `StatusLeds` to render.
+// ============================================================================
+
+/// One step of a single-LED on/off blink pattern.
+#[derive(Clone, Copy)]
+pub struct BlinkStep {
+    pub on: bool,
+    pub duration_ms: u64,
+}
+
+const fn blink(on: bool, duration_ms: u64) -> BlinkStep {
+    BlinkStep { on, duration_ms }
+}
+
+/// Pulse/gap timings for the profile-count prefix below, matching
+/// `render_profile_count`'s `PULSE_MS`/`GROUP_GAP_MS` on the dedicated
+/// pipeline so the two paths read the same on a stopwatch.
+const PROFILE_BLINK_MS: u64 = 150;
+const PROFILE_GROUP_GAP_MS: u64 = 600;
+
+/// Highest profile index this digital path bothers encoding a distinct
+/// blink count for. `StatusLeds` patterns are fixed `&'static` tables, not
+/// `render_profile_count`'s runtime loop, so every count up to this needs
+/// its own precomputed entry; profiles past it clamp to the longest entry
+/// rather than dropping the prefix like the old placeholder did.
+const MAX_DIGITAL_PROFILE: u8 = 7;
+const DIGITAL_PROFILE_COUNT: usize = MAX_DIGITAL_PROFILE as usize + 1;
+/// Longest a group (count blinks + closing gap + 2-step tail) ever gets, at
+/// `MAX_DIGITAL_PROFILE`.
+const MAX_PROFILE_GROUP_LEN: usize = 2 * DIGITAL_PROFILE_COUNT + 1 + 2;
+
+/// Builds `count` short blinks, a longer gap to close the group (the same
+/// encoding chunk0-2 established), then `tail`, padded to
+/// `MAX_PROFILE_GROUP_LEN` so every entry shares one array type; the second
+/// return value is how many of those entries are actually used.
+const fn profile_count_group(count: usize, tail: [BlinkStep; 2]) -> ([BlinkStep; MAX_PROFILE_GROUP_LEN], usize) {
+    let mut steps = [blink(false, 0); MAX_PROFILE_GROUP_LEN];
+    let mut i = 0;
+    while i < count {
+        steps[2 * i] = blink(true, PROFILE_BLINK_MS);
+        steps[2 * i + 1] = blink(false, PROFILE_BLINK_MS);
+        i += 1;
+    }
+    steps[2 * count] = blink(false, PROFILE_GROUP_GAP_MS);
+    steps[2 * count + 1] = tail[0];
+    steps[2 * count + 2] = tail[1];
+    (steps, 2 * count + 3)
+}
+
+const fn profile_count_groups(tail: [BlinkStep; 2]) -> [([BlinkStep; MAX_PROFILE_GROUP_LEN], usize); DIGITAL_PROFILE_COUNT] {
+    let mut groups = [([blink(false, 0); MAX_PROFILE_GROUP_LEN], 0); DIGITAL_PROFILE_COUNT];
+    let mut profile = 0;
+    while profile < DIGITAL_PROFILE_COUNT {
+        groups[profile] = profile_count_group(profile + 1, tail);
+        profile += 1;
+    }
+    groups
+}
+
+const ADVERTISING_TAIL: [BlinkStep; 2] = [blink(true, 500), blink(false, 500)];
+const CONNECTED_TAIL: [BlinkStep; 2] = [blink(true, 2000), blink(true, 60_000)];
+
+const ADVERTISING_PROFILE_GROUPS: [([BlinkStep; MAX_PROFILE_GROUP_LEN], usize); DIGITAL_PROFILE_COUNT] =
+    profile_count_groups(ADVERTISING_TAIL);
+const CONNECTED_PROFILE_GROUPS: [([BlinkStep; MAX_PROFILE_GROUP_LEN], usize); DIGITAL_PROFILE_COUNT] =
+    profile_count_groups(CONNECTED_TAIL);
+
+/// Picks this profile's pre-built blink-count-then-tail group, clamping to
+/// [`MAX_DIGITAL_PROFILE`].
+fn profile_group_steps(
+    profile: u8,
+    groups: &'static [([BlinkStep; MAX_PROFILE_GROUP_LEN], usize); DIGITAL_PROFILE_COUNT],
+) -> &'static [BlinkStep] {
+    let (steps, len) = &groups[profile.min(MAX_DIGITAL_PROFILE) as usize];
+    &steps[..*len]
+}
+
+/// Reduces a BLE pattern to on/off steps on a single LED, the same
+/// thresholding `GpioBackend` applies (any nonzero brightness is "on").
+/// Breathing can't be expressed digitally, so it collapses to a slow blink.
+///
+/// Unlike the dedicated pipeline, which blinks the profile count once per
+/// transition then settles into the steady cue, `StatusLeds` only ever
+/// holds one fixed `(steps, looped)` pair per indicator, so a looped group
+/// here replays the count prefix every cycle rather than once.
+pub fn ble_blink_pattern_for(state: BleLedState) -> (&'static [BlinkStep], bool) {
+    match state {
+        BleLedState::Idle => (&[blink(false, 1000)], true),
+        BleLedState::Advertising(profile) => (profile_group_steps(profile, &ADVERTISING_PROFILE_GROUPS), true),
+        BleLedState::ProfileSwitch => (
+            &[blink(true, 120), blink(false, 120), blink(true, 120), blink(false, 120)],
+            false,
+        ),
+        BleLedState::Connected(profile) => (profile_group_steps(profile, &CONNECTED_PROFILE_GROUPS), true),
+        BleLedState::Dropped => (
+            &[
+                blink(true, 80),
+                blink(false, 80),
+                blink(true, 80),
+                blink(false, 80),
+                blink(true, 80),
+                blink(false, 80),
+            ],
+            false,
+        ),
+        BleLedState::Charging => (&[blink(true, 60_000)], true),
+        BleLedState::FullCharge => (
+            &[
+                blink(true, 60),
+                blink(false, 60),
+                blink(true, 60),
+                blink(false, 60),
+            ],
+            false,
+        ),
+        // This digital path can't carry the configured pulse interval the
+        // way `render_low_battery_pulse` does, so it blinks at a fixed
+        // fallback rate instead.
+        BleLedState::LowBattery(_) => (&[blink(true, 150), blink(false, 850)], true),
+    }
+}
+
+/// Named indicator an application registers with [`StatusLeds`]. Several
+/// indicators can target the same physical LED; the highest-priority one
+/// with an active request wins (e.g. a low-battery warning overrides BLE
+/// connection status).
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum IndicatorName {
+    Battery,
+    Charging,
+    Ble,
+    LayerLock,
+}
+
+/// Priority order, highest first: a low-battery warning should never be
+/// hidden by a routine BLE blink, but a layer-lock cue is the least urgent.
+const INDICATOR_PRIORITY: [IndicatorName; 4] = [
+    IndicatorName::Battery,
+    IndicatorName::Charging,
+    IndicatorName::Ble,
+    IndicatorName::LayerLock,
+];
+
+/// Walks [`INDICATOR_PRIORITY`] and returns the first present request,
+/// indexed by `IndicatorName as usize`. The one priority rule, shared by
+/// [`StatusLeds::resolve`] and `BleConnectionLed`'s battery-vs-BLE
+/// arbitration, so the two don't drift into separate rules for the same
+/// Battery/Charging/Ble precedence.
+fn resolve_by_priority<T: Copy>(requests: &[Option<T>; 4]) -> Option<T> {
+    for &name in &INDICATOR_PRIORITY {
+        if let Some(value) = requests[name as usize] {
+            return Some(value);
+        }
+    }
+    None
+}
+
+struct RunningPattern {
+    steps: &'static [BlinkStep],
+    looped: bool,
+    index: usize,
+    elapsed_ms: u64,
+}
+
+/// Thin wrapper over a single physical LED pin, independent of what it
+/// indicates: [`StatusLeds`] composes several of these under priority.
+pub struct StatusLed<P: OutputPin> {
+    pin: P,
+    active_low: bool,
+    level: bool,
+    pattern: Option<RunningPattern>,
+}
+
+impl<P: OutputPin> StatusLed<P> {
+    pub fn new(pin: P, active_low: bool) -> Self {
+        Self {
+            pin,
+            active_low,
+            level: false,
+            pattern: None,
+        }
+    }
+
+    fn drive(&mut self, on: bool) {
+        self.level = on;
+        let set_high = on != self.active_low;
+        let _ = if set_high {
+            self.pin.set_high()
+        } else {
+            self.pin.set_low()
+        };
+    }
+
+    pub fn on(&mut self) {
+        self.pattern = None;
+        self.drive(true);
+    }
+
+    pub fn off(&mut self) {
+        self.pattern = None;
+        self.drive(false);
+    }
+
+    pub fn toggle(&mut self) {
+        self.pattern = None;
+        let level = self.level;
+        self.drive(!level);
+    }
+
+    /// Install a blink pattern, replacing whatever was running before.
+    pub fn set_pattern(&mut self, steps: &'static [BlinkStep], looped: bool) {
+        let first_on = steps.first().is_some_and(|s| s.on);
+        self.drive(first_on);
+        self.pattern = Some(RunningPattern {
+            steps,
+            looped,
+            index: 0,
+            elapsed_ms: 0,
+        });
+    }
+
+    /// Advance the running pattern, if any, by `dt_ms`.
+    fn tick(&mut self, dt_ms: u64) {
+        let Some(pattern) = &mut self.pattern else {
+            return;
+        };
+
+        pattern.elapsed_ms += dt_ms;
+        if pattern.elapsed_ms < pattern.steps[pattern.index].duration_ms {
+            return;
+        }
+        pattern.elapsed_ms = 0;
+        pattern.index += 1;
+
+        if pattern.index >= pattern.steps.len() {
+            if pattern.looped {
+                pattern.index = 0;
+            } else {
+                self.pattern = None;
+                self.drive(false);
+                return;
+            }
+        }
+
+        let on = pattern.steps[pattern.index].on;
+        self.drive(on);
+    }
+}
+
+/// Registers several named indicators and multiplexes them onto `N` physical
+/// LEDs by priority, so adding a new indicator is a few lines instead of a
+/// new controller. `BleConnectionLed` can feed `IndicatorName::Ble` here via
+/// [`ble_blink_pattern_for`] for boards that don't need the dedicated
+/// breathing pipeline ([`render_ble_led`]) below.
+pub struct StatusLeds<P: OutputPin, const N: usize> {
+    leds: [StatusLed<P>; N],
+    /// Per-LED, per-indicator requests: `requests[led_index][indicator]`.
+    /// Keyed by LED too, not just indicator, since two different LEDs can
+    /// each have their own active request from the same indicator (e.g. a
+    /// board with a separate Battery LED per half).
+    requests: [[Option<(&'static [BlinkStep], bool)>; 4]; N],
+}
+
+impl<P: OutputPin, const N: usize> StatusLeds<P, N> {
+    pub fn new(leds: [StatusLed<P>; N]) -> Self {
+        Self {
+            leds,
+            requests: [[None; 4]; N],
+        }
+    }
+
+    /// Register (or clear, with `None`) what `indicator` wants shown on
+    /// `led_index`, then re-resolve that LED against the priority order.
+    pub fn set(
+        &mut self,
+        indicator: IndicatorName,
+        led_index: usize,
+        request: Option<(&'static [BlinkStep], bool)>,
+    ) {
+        self.requests[led_index][indicator as usize] = request;
+        self.resolve(led_index);
+    }
+
+    fn resolve(&mut self, led_index: usize) {
+        match resolve_by_priority(&self.requests[led_index]) {
+            Some((steps, looped)) => self.leds[led_index].set_pattern(steps, looped),
+            None => self.leds[led_index].off(),
+        }
+    }
+
+    /// Advance every LED's running pattern by `dt_ms`. Call this once per
+    /// tick from a single shared render task.
+    pub fn tick(&mut self, dt_ms: u64) {
+        for led in &mut self.leds {
+            led.tick(dt_ms);
+        }
+    }
+}
+
+/// Whether a resolved `BleLedState` is the "connection dropped" cue (red) or
+/// a routine one (blue): a dropped connection is the one case that must
+/// stay visually distinct from everything else, the same blue-vs-red split
+/// [`render_ble_led`]'s two-LED backend renders.
+fn is_dropped_cue(state: BleLedState) -> bool {
+    matches!(state, BleLedState::Dropped)
+}
+
+/// Single shared render task for a [`StatusLeds`] container: ticks every
+/// registered LED at a fixed cadence so a board only spawns one task no
+/// matter how many indicators it registers.
+///
+/// `ble_led_indices`, when set, also polls [`take_ble_status`] each tick and
+/// feeds any new state into `IndicatorName::Ble` on one of the two given LED
+/// indices — `(blue_index, red_index)` — for a board that wired a
+/// [`BleConnectionLed`] with [`stash_ble_status`] as its sink so BLE status
+/// composes with this container's other indicators while keeping the same
+/// blue-vs-red distinction [`render_ble_led`] renders: routine states
+/// (idle, advertising, connected, ...) go to `blue_index`, a dropped
+/// connection goes to `red_index`, and the sibling index is cleared so a
+/// stale pattern doesn't keep running on the LED that no longer applies.
+pub async fn run_status_leds<P: OutputPin, const N: usize>(
+    mut leds: StatusLeds<P, N>,
+    tick_interval: Duration,
+    ble_led_indices: Option<(usize, usize)>,
+) -> ! {
+    let tick_ms = tick_interval.as_millis();
+    let mut last_ble_state = None;
+    loop {
+        Timer::after(tick_interval).await;
+        if let Some((blue_index, red_index)) = ble_led_indices {
+            if let Some(state) = take_ble_status() {
+                if last_ble_state != Some(state) {
+                    last_ble_state = Some(state);
+                    let pattern = Some(ble_blink_pattern_for(state));
+                    let (active_index, idle_index) = if is_dropped_cue(state) {
+                        (red_index, blue_index)
+                    } else {
+                        (blue_index, red_index)
+                    };
+                    leds.set(IndicatorName::Ble, active_index, pattern);
+                    leds.set(IndicatorName::Ble, idle_index, None);
+                }
+            }
+        }
+        leds.tick(tick_ms);
+    }
+}
+
+/// Drives the two connection LEDs at a given brightness, so
+/// [`render_ble_led`] doesn't care whether the board has a free PWM channel.
+pub trait ConnectionLedBackend {
+    fn set(&mut self, blue: u8, red: u8);
+}
+
+/// Plain digital GPIO backend: any brightness > 0 is full on. The fallback
+/// for boards without a free PWM channel; breathing still runs, it just
+/// can't dim.
+pub struct GpioBackend {
     led_blue: Output<'static>,
     led_red: Output<'static>,
+}
+
+impl GpioBackend {
+    pub fn new(led_blue: Output<'static>, led_red: Output<'static>) -> Self {
+        Self { led_blue, led_red }
+    }
+}
+
+impl ConnectionLedBackend for GpioBackend {
+    fn set(&mut self, blue: u8, red: u8) {
+        self.led_blue.set_level(if blue > 0 { Level::Low } else { Level::High });
+        self.led_red.set_level(if red > 0 { Level::Low } else { Level::High });
+    }
+}
+
+/// PWM-backed backend: maps brightness straight to duty cycle, so the
+/// breathing effect actually dims instead of just flashing. Gated behind
+/// `pwm-led` since it pulls in `embassy_nrf::pwm`; channel 0 drives blue,
+/// channel 1 drives red.
+#[cfg(feature = "pwm-led")]
+pub struct PwmBackend<'d, T: embassy_nrf::pwm::Instance> {
+    pwm: embassy_nrf::pwm::SimplePwm<'d, T>,
+    max_duty: u16,
+}
+
+#[cfg(feature = "pwm-led")]
+impl<'d, T: embassy_nrf::pwm::Instance> PwmBackend<'d, T> {
+    pub fn new(pwm: embassy_nrf::pwm::SimplePwm<'d, T>) -> Self {
+        let max_duty = pwm.max_duty();
+        Self { pwm, max_duty }
+    }
+}
+
+#[cfg(feature = "pwm-led")]
+impl<'d, T: embassy_nrf::pwm::Instance> ConnectionLedBackend for PwmBackend<'d, T> {
+    fn set(&mut self, blue: u8, red: u8) {
+        let duty = |level: u8| (level as u32 * self.max_duty as u32 / 255) as u16;
+        self.pwm.set_duty(0, duty(blue));
+        self.pwm.set_duty(1, duty(red));
+    }
+}
+
+/// Queue of BLE lifecycle transitions, fed by [`BleConnectionLed::process_event`]
+/// and drained by [`run_ble_connection_led`]. Keeping the pattern renderer in
+/// its own task means the `#[controller(event)]` hook only ever pushes a
+/// state and returns, never sleeping through a blink or pulse itself.
+static BLE_LED_CHANNEL: Channel<CriticalSectionRawMutex, BleLedState, 4> = Channel::new();
+
+/// Latest `BleLedState` resolved by a [`BleConnectionLed`] that was given
+/// [`stash_ble_status`] as its sink, for a board that polls it into a
+/// [`StatusLeds`] container's `IndicatorName::Ble` slot instead of driving
+/// [`BLE_LED_CHANNEL`] directly.
+static BLE_STATUS_SLOT: Mutex<CriticalSectionRawMutex, Cell<Option<BleLedState>>> =
+    Mutex::new(Cell::new(None));
+
+/// [`BleConnectionLed::set_status_sink`] callback that stashes the resolved
+/// state for a board's render task to pick up via [`take_ble_status`].
+pub fn stash_ble_status(state: BleLedState) {
+    BLE_STATUS_SLOT.lock(|cell| cell.set(Some(state)));
+}
+
+/// Takes (and clears) the latest state stashed by [`stash_ble_status`], if
+/// any new one has arrived since the last call.
+pub fn take_ble_status() -> Option<BleLedState> {
+    BLE_STATUS_SLOT.lock(|cell| cell.take())
+}
+
+/// Dispatches to whichever renderer the latest `BleLedState` needs: the
+/// static [`Pattern`] table for most states, or one of the dynamic
+/// renderers below for states that carry a runtime value (a profile index
+/// or battery-pulse interval) a `'static` pattern can't express.
+async fn render_ble_led<B: ConnectionLedBackend>(mut backend: B) {
+    let mut state = BleLedState::Idle;
+    loop {
+        state = match state {
+            BleLedState::LowBattery(pulse_ms) => render_low_battery_pulse(&mut backend, pulse_ms).await,
+            BleLedState::Connected(profile) => match render_profile_count(&mut backend, profile).await {
+                Some(next) => next,
+                None => render_pattern(&mut backend, CONNECTED_PATTERN).await,
+            },
+            BleLedState::Advertising(profile) => match render_profile_count(&mut backend, profile).await {
+                Some(next) => next,
+                None => render_pattern(&mut backend, ADVERTISING_PATTERN).await,
+            },
+            other => render_pattern(&mut backend, pattern_for(other)).await,
+        };
+    }
+}
+
+/// Renders a single [`Pattern`] onto `backend`, stepping through it on an
+/// `embassy_time::Timer` race against the next incoming state so a new
+/// transition always preempts a pattern already in flight. Returns the
+/// state that preempted it (or that follows a completed one-shot pattern).
+async fn render_pattern<B: ConnectionLedBackend>(backend: &mut B, pattern: Pattern) -> BleLedState {
+    match pattern {
+        Pattern::Steps { steps, looped } => {
+            let mut index = 0;
+            loop {
+                let s = steps[index];
+                backend.set(s.blue_level, s.red_level);
+
+                let delay = Duration::from_millis(s.duration_ms);
+                match select(BLE_LED_CHANNEL.receive(), Timer::after(delay)).await {
+                    Either::First(new_state) => return new_state,
+                    Either::Second(_) => {
+                        index += 1;
+                        if index >= steps.len() {
+                            if looped {
+                                index = 0;
+                            } else {
+                                backend.set(0, 0);
+                                return BLE_LED_CHANNEL.receive().await;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Pattern::Breathing { channel, period_ms } => {
+            let tick_ms = (period_ms / GAMMA_TABLE.len() as u64).max(1);
+            let mut i = 0;
+            loop {
+                let level = GAMMA_TABLE[i];
+                match channel {
+                    LedChannel::Blue => backend.set(level, 0),
+                    LedChannel::Red => backend.set(0, level),
+                }
+
+                match select(BLE_LED_CHANNEL.receive(), Timer::after(Duration::from_millis(tick_ms))).await {
+                    Either::First(new_state) => return new_state,
+                    Either::Second(_) => {
+                        i = (i + 1) % GAMMA_TABLE.len();
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Blinks the blue LED `profile + 1` times, the same encoding chunk0-2
+/// established so the operator can read the active BLE profile index
+/// directly off the LED, before the caller falls through to the steady
+/// connected/advertising pattern. Returns `Some` if a new state preempted
+/// the blink sequence, `None` if it ran to completion undisturbed.
+async fn render_profile_count<B: ConnectionLedBackend>(backend: &mut B, profile: u8) -> Option<BleLedState> {
+    const PULSE_MS: u64 = 150;
+    const GROUP_GAP_MS: u64 = 600;
+
+    for _ in 0..=profile {
+        backend.set(255, 0);
+        if let Either::First(new_state) =
+            select(BLE_LED_CHANNEL.receive(), Timer::after(Duration::from_millis(PULSE_MS))).await
+        {
+            return Some(new_state);
+        }
+
+        backend.set(0, 0);
+        if let Either::First(new_state) =
+            select(BLE_LED_CHANNEL.receive(), Timer::after(Duration::from_millis(PULSE_MS))).await
+        {
+            return Some(new_state);
+        }
+    }
+
+    if let Either::First(new_state) =
+        select(BLE_LED_CHANNEL.receive(), Timer::after(Duration::from_millis(GROUP_GAP_MS))).await
+    {
+        return Some(new_state);
+    }
+    None
+}
+
+/// Renders the low-battery pulse: a brief red flash every `pulse_ms`. Kept
+/// out of the `Pattern`/`pattern_for` table since its period is a runtime
+/// value carried on `BleLedState::LowBattery` rather than a `'static` const.
+async fn render_low_battery_pulse<B: ConnectionLedBackend>(backend: &mut B, pulse_ms: u64) -> BleLedState {
+    const PULSE_ON_MS: u64 = 150;
+    loop {
+        backend.set(0, 255);
+        if let Either::First(new_state) =
+            select(BLE_LED_CHANNEL.receive(), Timer::after(Duration::from_millis(PULSE_ON_MS))).await
+        {
+            return new_state;
+        }
+
+        backend.set(0, 0);
+        let rest_ms = pulse_ms.saturating_sub(PULSE_ON_MS).max(1);
+        if let Either::First(new_state) =
+            select(BLE_LED_CHANNEL.receive(), Timer::after(Duration::from_millis(rest_ms))).await
+        {
+            return new_state;
+        }
+    }
+}
+
+/// Owns the two physical LEDs over a plain GPIO backend. Spawn this once per
+/// board alongside the `BleConnectionLed` controller; use
+/// [`run_ble_connection_led_pwm`] instead on a board with a free PWM channel
+/// for proper dimming.
+#[embassy_executor::task]
+pub async fn run_ble_connection_led(led_blue: Output<'static>, led_red: Output<'static>) {
+    render_ble_led(GpioBackend::new(led_blue, led_red)).await;
+}
+
+/// Same as [`run_ble_connection_led`], but over a `SimplePwm` backend so the
+/// advertising breathing effect actually dims instead of just flashing.
+#[cfg(feature = "pwm-led")]
+#[embassy_executor::task]
+pub async fn run_ble_connection_led_pwm(
+    pwm: embassy_nrf::pwm::SimplePwm<'static, embassy_nrf::peripherals::PWM0>,
+) {
+    render_ble_led(PwmBackend::new(pwm)).await;
+}
+
+/// Battery percentage at or above which charging is shown as "full" rather
+/// than "topping up".
+const FULL_CHARGE_PERCENT: u8 = 100;
+
+pub struct BleConnectionLed {
     sub: ControllerSub,
     last_profile: Option<u8>,
     last_connected: Option<bool>,
+    low_battery_threshold: u8,
+    low_battery_pulse_ms: u64,
+    charging: bool,
+    battery_percent: u8,
+    /// Optional sink a board registers with [`BleConnectionLed::set_status_sink`]
+    /// so its resolved state also reaches a [`StatusLeds`] container (via
+    /// [`stash_ble_status`]/[`ble_blink_pattern_for`]), for boards that
+    /// compose BLE status with other indicators on the digital subsystem
+    /// instead of the dedicated breathing pipeline on [`BLE_LED_CHANNEL`].
+    status_sink: Option<fn(BleLedState)>,
 }
 
 impl BleConnectionLed {
-    pub fn new(led_blue: Output<'static>, led_red: Output<'static>) -> Self {
+    /// `low_battery_threshold` is the percentage below which the red pulse
+    /// takes over from whatever BLE cue would otherwise show;
+    /// `low_battery_pulse_interval` is how often it repeats.
+    pub fn new(low_battery_threshold: u8, low_battery_pulse_interval: Duration) -> Self {
         Self {
-            led_blue,
-            led_red,
             sub: unwrap!(CONTROLLER_CHANNEL.subscriber()),
             last_profile: None,
             last_connected: None,
+            low_battery_threshold,
+            low_battery_pulse_ms: low_battery_pulse_interval.as_millis(),
+            charging: false,
+            battery_percent: 100,
+            status_sink: None,
         }
     }
 
-    async fn indicate(&mut self, state: BleState) {
-        match state {
-            BleState::Connected => {
-                self.led_blue.set_level(Level::Low);
-                info!("BLE connected, Blue LED ON");
-                Timer::after_millis(500).await;
-                self.led_blue.set_level(Level::High);
-                info!("Blue LED OFF after 500ms");
+    /// Registers a sink called with every resolved `BleLedState` in place of
+    /// the send to `BLE_LED_CHANNEL`, e.g. [`stash_ble_status`] for a board
+    /// that renders BLE status through a [`StatusLeds`] container instead of
+    /// the dedicated breathing pipeline. `BLE_LED_CHANNEL` only has a
+    /// consumer when [`run_ble_connection_led`] is spawned, so once a sink is
+    /// registered `publish` stops feeding the channel — sending there too
+    /// would fill its fixed capacity and block this controller forever.
+    pub fn set_status_sink(&mut self, sink: fn(BleLedState)) {
+        self.status_sink = Some(sink);
+    }
+
+    /// Updates the arbitrated battery/charging state and republishes
+    /// whichever `BleLedState` that leaves in front. `rmk::event::ControllerEvent`
+    /// doesn't carry battery data as of this writing (only `BleState`,
+    /// `SplitCentral` and `Layer` are confirmed), so this can't be wired
+    /// through `process_event` the way the BLE transitions are. Call this
+    /// directly from wherever this board's battery monitoring actually
+    /// lives once that source exists; until then `battery_percent` stays at
+    /// its `new` default and `LowBattery`/`Charging` never arbitrate in.
+    pub async fn set_battery_state(&mut self, percent: u8, charging: bool) {
+        self.battery_percent = percent;
+        self.charging = charging;
+        let led_state = self.resolve_state(None);
+        self.publish(led_state).await;
+    }
+
+    /// Routes a resolved state to whichever single consumer this board
+    /// wired: the `status_sink`, when registered, or `BLE_LED_CHANNEL`
+    /// otherwise.
+    async fn publish(&self, led_state: BleLedState) {
+        if let Some(sink) = self.status_sink {
+            sink(led_state);
+        } else {
+            BLE_LED_CHANNEL.send(led_state).await;
+        }
+    }
+
+    fn indicate(&mut self, profile: u8, state: BleState) -> BleLedState {
+        let connected_now = matches!(state, BleState::Connected);
+        let profile_changed = self.last_profile != Some(profile);
+        let was_connected = self.last_connected == Some(true);
+
+        if connected_now {
+            if profile_changed && was_connected {
+                info!("BLE profile switched to {}, double-blinking blue LED", profile);
+                BleLedState::ProfileSwitch
+            } else {
+                info!(
+                    "BLE connected on profile {}, blinking count then solid blue for 2s",
+                    profile
+                );
+                BleLedState::Connected(profile)
             }
-            BleState::None | BleState::Advertising => {
-                self.led_red.set_level(Level::Low);
-                info!("BLE not connected, Red LED ON");
-                Timer::after_millis(500).await;
-                self.led_red.set_level(Level::High);
-                info!("Red LED OFF after 500ms");
+        } else if was_connected {
+            info!("BLE connection on profile {} dropped, flashing red", profile);
+            BleLedState::Dropped
+        } else {
+            info!(
+                "BLE advertising on profile {}, blinking count then pulsing blue",
+                profile
+            );
+            BleLedState::Advertising(profile)
+        }
+    }
+
+    /// Arbitrates the next state to render: whatever BLE transition just
+    /// happened (`ble_led_state`, when there is one) against the current
+    /// battery/charging state, using the same [`resolve_by_priority`] rule
+    /// (and `IndicatorName`/`INDICATOR_PRIORITY` table) that `StatusLeds`
+    /// uses for its indicators, rather than a second hand-rolled priority
+    /// check.
+    fn resolve_state(&self, ble_led_state: Option<BleLedState>) -> BleLedState {
+        let mut requests: [Option<BleLedState>; 4] = [None; 4];
+
+        requests[IndicatorName::Battery as usize] = (!self.charging
+            && self.battery_percent < self.low_battery_threshold)
+            .then(|| BleLedState::LowBattery(self.low_battery_pulse_ms));
+
+        requests[IndicatorName::Charging as usize] = self.charging.then(|| {
+            if self.battery_percent >= FULL_CHARGE_PERCENT {
+                BleLedState::FullCharge
+            } else {
+                BleLedState::Charging
             }
+        });
+
+        requests[IndicatorName::Ble as usize] =
+            Some(ble_led_state.unwrap_or_else(|| self.steady_ble_state()));
+
+        resolve_by_priority(&requests).unwrap_or(BleLedState::Idle)
+    }
+
+    /// The BLE-only cue for the last known connection state, used when a
+    /// battery event clears and a routine BLE cue should resume instead of
+    /// re-running a one-shot transition.
+    fn steady_ble_state(&self) -> BleLedState {
+        let profile = self.last_profile.unwrap_or(0);
+        if self.last_connected == Some(true) {
+            BleLedState::Connected(profile)
+        } else {
+            BleLedState::Advertising(profile)
         }
     }
 }
 
+impl Default for BleConnectionLed {
+    fn default() -> Self {
+        Self::new(20, Duration::from_secs(5))
+    }
+}
+
 impl Controller for BleConnectionLed {
     type Event = ControllerEvent;
 
@@ -57,11 +921,20 @@ impl Controller for BleConnectionLed {
                 let profile_changed = self.last_profile != Some(profile_id);
                 let state_changed = self.last_connected != Some(connected_now);
 
+                // `indicate` reads `last_profile`/`last_connected` to tell a
+                // profile switch or a drop apart from a routine
+                // connect/advertise, so it must run against the prior state —
+                // updating them first would make its own checks always see
+                // the value it was just asked to compare against.
+                let ble_led_state =
+                    (first || profile_changed || state_changed).then(|| self.indicate(profile_id, state));
+
                 self.last_profile = Some(profile_id);
                 self.last_connected = Some(connected_now);
 
-                if first || profile_changed || state_changed {
-                    self.indicate(state).await;
+                if let Some(ble_led_state) = ble_led_state {
+                    let led_state = self.resolve_state(Some(ble_led_state));
+                    self.publish(led_state).await;
                 }
             }
             _ => {}
@@ -77,14 +950,41 @@ pub struct SplitConnectionLed {
     led_blue: Output<'static>,
     led_red: Output<'static>,
     sub: ControllerSub,
+    /// LED currently held low for the 500ms pulse kicked off by `indicate`,
+    /// if any.
+    pending: Option<LedChannel>,
 }
 
 impl SplitConnectionLed {
+    const PULSE_MS: u64 = 500;
+
     pub fn new(led_blue: Output<'static>, led_red: Output<'static>) -> Self {
         Self {
             led_blue,
             led_red,
             sub: unwrap!(CONTROLLER_CHANNEL.subscriber()),
+            pending: None,
+        }
+    }
+
+    fn led_mut(&mut self, channel: LedChannel) -> &mut Output<'static> {
+        match channel {
+            LedChannel::Blue => &mut self.led_blue,
+            LedChannel::Red => &mut self.led_red,
+        }
+    }
+
+    fn indicate(&mut self, connected: bool) {
+        let channel = if connected { LedChannel::Blue } else { LedChannel::Red };
+        info!("Split {}, LED ON", if connected { "connected" } else { "disconnected" });
+        self.led_mut(channel).set_level(Level::Low);
+        self.pending = Some(channel);
+    }
+
+    fn step_blink(&mut self) {
+        if let Some(channel) = self.pending.take() {
+            self.led_mut(channel).set_level(Level::High);
+            info!("LED OFF after 500ms");
         }
     }
 }
@@ -93,27 +993,223 @@ impl Controller for SplitConnectionLed {
     type Event = ControllerEvent;
 
     async fn process_event(&mut self, event: Self::Event) {
-        match event {
-            ControllerEvent::SplitCentral(connected) => {
-                if connected {
-                    self.led_blue.set_level(Level::Low);
-                    info!("Split connected, Blue LED ON");
-                    Timer::after_millis(500).await;
-                    self.led_blue.set_level(Level::High);
-                    info!("Blue LED OFF after 500ms");
-                } else if !connected {
-                    self.led_red.set_level(Level::Low);
-                    info!("Split disconnected, Red LED ON");
-                    Timer::after_millis(500).await;
-                    self.led_red.set_level(Level::High);
-                    info!("Red LED OFF after 500ms");
+        if let ControllerEvent::SplitCentral(connected) = event {
+            self.indicate(connected);
+        }
+    }
+
+    /// Non-blocking: the pending blink's timer races the event channel so a
+    /// rapid reconnect isn't swallowed by the 500ms hold.
+    async fn next_message(&mut self) -> Self::Event {
+        loop {
+            if self.pending.is_some() {
+                match select(
+                    self.sub.next_message_pure(),
+                    Timer::after(Duration::from_millis(Self::PULSE_MS)),
+                )
+                .await
+                {
+                    Either::First(event) => return event,
+                    Either::Second(_) => self.step_blink(),
                 }
+            } else {
+                return self.sub.next_message_pure().await;
             }
-            _ => {}
         }
     }
+}
 
-    async fn next_message(&mut self) -> Self::Event {
-        self.sub.next_message_pure().await
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+
+    /// Records the last level driven, the minimal fake [`OutputPin`] needed
+    /// to drive [`StatusLed`]/[`StatusLeds`] without real hardware.
+    struct TestPin {
+        high: bool,
+    }
+
+    impl TestPin {
+        fn new() -> Self {
+            Self { high: false }
+        }
+    }
+
+    impl embedded_hal::digital::ErrorType for TestPin {
+        type Error = core::convert::Infallible;
+    }
+
+    impl OutputPin for TestPin {
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            self.high = true;
+            Ok(())
+        }
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            self.high = false;
+            Ok(())
+        }
+    }
+
+    const BATTERY_PATTERN: (&[BlinkStep], bool) = (&[blink(false, 10)], true);
+    const BLE_PATTERN: (&[BlinkStep], bool) = (&[blink(true, 20)], false);
+    const LAYER_LOCK_PATTERN: (&[BlinkStep], bool) = (&[blink(true, 30)], false);
+
+    #[test]
+    fn resolve_by_priority_prefers_battery_over_everything_else() {
+        let mut requests = [None; 4];
+        requests[IndicatorName::Ble as usize] = Some(BLE_PATTERN);
+        requests[IndicatorName::LayerLock as usize] = Some(LAYER_LOCK_PATTERN);
+        requests[IndicatorName::Battery as usize] = Some(BATTERY_PATTERN);
+
+        assert!(matches!(resolve_by_priority(&requests), Some(p) if p.0[0].duration_ms == BATTERY_PATTERN.0[0].duration_ms));
+    }
+
+    #[test]
+    fn resolve_by_priority_falls_through_to_the_next_present_request() {
+        let mut requests = [None; 4];
+        requests[IndicatorName::Ble as usize] = Some(BLE_PATTERN);
+        requests[IndicatorName::LayerLock as usize] = Some(LAYER_LOCK_PATTERN);
+
+        assert!(matches!(resolve_by_priority(&requests), Some(p) if p.0[0].duration_ms == BLE_PATTERN.0[0].duration_ms));
+    }
+
+    #[test]
+    fn resolve_by_priority_is_none_when_nothing_is_requested() {
+        let requests: [Option<(&'static [BlinkStep], bool)>; 4] = [None; 4];
+
+        assert!(resolve_by_priority(&requests).is_none());
+    }
+
+    #[test]
+    fn status_leds_set_reresolves_the_led_with_the_new_priority_winner() {
+        let mut leds = StatusLeds::new([StatusLed::new(TestPin::new(), false)]);
+
+        leds.set(IndicatorName::Ble, 0, Some(BLE_PATTERN));
+        assert!(leds.leds[0].pin.high);
+
+        // Battery outranks BLE, so registering it takes over the LED even
+        // though the BLE request is still pending.
+        leds.set(IndicatorName::Battery, 0, Some(BATTERY_PATTERN));
+        assert!(!leds.leds[0].pin.high);
+
+        // Battery clears; the LED should fall back to the still-registered
+        // BLE request rather than turning off.
+        leds.set(IndicatorName::Battery, 0, None);
+        assert!(leds.leds[0].pin.high);
+
+        leds.set(IndicatorName::Ble, 0, None);
+        assert!(!leds.leds[0].pin.high);
+    }
+
+    #[test]
+    fn status_leds_keys_requests_per_led_not_just_per_indicator() {
+        let mut leds = StatusLeds::new([StatusLed::new(TestPin::new(), false), StatusLed::new(TestPin::new(), false)]);
+
+        leds.set(IndicatorName::Battery, 0, Some(BLE_PATTERN));
+
+        assert!(leds.leds[0].pin.high);
+        assert!(!leds.leds[1].pin.high);
+    }
+
+    std::thread_local! {
+        /// Per-thread (cargo test gives each test its own thread) stand-in
+        /// for a board's status sink, so `process_event` tests can observe
+        /// the published `BleLedState` without going through
+        /// `BLE_LED_CHANNEL` (a global, capacity-4 channel with no receiver
+        /// in these tests, which `.send().await` would eventually block on).
+        static SINK_SLOT: Cell<Option<BleLedState>> = Cell::new(None);
+    }
+
+    fn test_sink(state: BleLedState) {
+        SINK_SLOT.with(|slot| slot.set(Some(state)));
+    }
+
+    fn take_sink() -> Option<BleLedState> {
+        SINK_SLOT.with(|slot| slot.take())
+    }
+
+    #[test]
+    fn process_event_profile_switch_while_connected_is_a_profile_switch_not_a_plain_connect() {
+        let mut led = BleConnectionLed::new(20, Duration::from_secs(5));
+        led.set_status_sink(test_sink);
+
+        block_on(led.process_event(ControllerEvent::BleState(0, BleState::Connected)));
+        assert!(matches!(take_sink(), Some(BleLedState::Connected(0))));
+
+        // Still connected, but the profile changed underneath it — this
+        // must read as `ProfileSwitch`, not re-derive `Connected(1)` as if
+        // it were a fresh connection.
+        block_on(led.process_event(ControllerEvent::BleState(1, BleState::Connected)));
+        assert!(matches!(take_sink(), Some(BleLedState::ProfileSwitch)));
+    }
+
+    #[test]
+    fn process_event_drop_while_connected_is_a_drop_not_advertising() {
+        let mut led = BleConnectionLed::new(20, Duration::from_secs(5));
+        led.set_status_sink(test_sink);
+
+        block_on(led.process_event(ControllerEvent::BleState(0, BleState::Connected)));
+        assert!(matches!(take_sink(), Some(BleLedState::Connected(0))));
+
+        // Same profile, no longer connected — this must read as `Dropped`,
+        // not `Advertising` as if it had never connected on this profile.
+        block_on(led.process_event(ControllerEvent::BleState(0, BleState::Advertising)));
+        assert!(matches!(take_sink(), Some(BleLedState::Dropped)));
+    }
+
+    #[test]
+    fn resolve_state_battery_overrides_ble_connection_status() {
+        let mut led = BleConnectionLed::new(20, Duration::from_secs(5));
+        led.last_profile = Some(0);
+        led.last_connected = Some(true);
+
+        assert!(matches!(led.resolve_state(None), BleLedState::Connected(0)));
+
+        led.battery_percent = 10;
+        assert!(matches!(led.resolve_state(None), BleLedState::LowBattery(_)));
+    }
+
+    #[test]
+    fn resolve_state_charging_overrides_ble_even_below_low_battery_threshold() {
+        let mut led = BleConnectionLed::new(20, Duration::from_secs(5));
+        led.last_profile = Some(0);
+        led.last_connected = Some(false);
+        led.battery_percent = 10;
+        led.charging = true;
+
+        assert!(matches!(led.resolve_state(None), BleLedState::Charging));
+    }
+
+    #[test]
+    fn resolve_state_falls_back_to_ble_once_battery_clears() {
+        let mut led = BleConnectionLed::new(20, Duration::from_secs(5));
+        led.last_profile = Some(2);
+        led.last_connected = Some(true);
+        led.battery_percent = 10;
+
+        assert!(matches!(led.resolve_state(None), BleLedState::LowBattery(_)));
+
+        led.battery_percent = 100;
+        assert!(matches!(led.resolve_state(None), BleLedState::Connected(2)));
+    }
+
+    #[test]
+    fn resolve_state_prefers_the_given_ble_transition_over_the_steady_state() {
+        let mut led = BleConnectionLed::new(20, Duration::from_secs(5));
+        led.last_profile = Some(0);
+        led.last_connected = Some(true);
+
+        assert!(matches!(led.resolve_state(Some(BleLedState::Dropped)), BleLedState::Dropped));
+    }
+
+    #[test]
+    fn steady_ble_state_reflects_last_connection_outcome() {
+        let mut led = BleConnectionLed::new(20, Duration::from_secs(5));
+        assert!(matches!(led.steady_ble_state(), BleLedState::Advertising(0)));
+
+        led.last_profile = Some(1);
+        led.last_connected = Some(true);
+        assert!(matches!(led.steady_ble_state(), BleLedState::Connected(1)));
     }
 }
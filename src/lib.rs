@@ -7,11 +7,14 @@
 // Ported from the Zephyr driver implementation:
 // https://github.com/zephyrproject-rtos/zephyr/blob/d31c6e95033fd6b3763389edba6a655245ae1328/drivers/input/input_pmw3610.c
 //
-// Note: This implementation uses half-duplex SPI (single bidirectional data line) via bit-banging.
+// Note: The driver is generic over its byte transport (see `Pmw3610Transport`);
+// `BitBangTransport` drives half-duplex SPI over a single bidirectional data line,
+// and `SpiBusTransport` drives a hardware 3-wire/half-duplex SPI peripheral.
 
-#![no_std]
+#![cfg_attr(not(test), no_std)]
 
 use defmt::{debug, error, info, warn, Format};
+use embassy_futures::select::{select, Either};
 use embassy_time::{Duration, Timer};
 use embedded_hal::digital::{InputPin, OutputPin};
 
@@ -65,7 +68,6 @@ const PMW3610_REST1_DOWNSHIFT: u8 = 0x1d;
 const PMW3610_OBSERVATION1: u8 = 0x2d;
 const PMW3610_SMART_MODE: u8 = 0x32;
 const PMW3610_POWER_UP_RESET: u8 = 0x3a;
-#[allow(dead_code)]
 const PMW3610_SHUTDOWN: u8 = 0x3b;
 const PMW3610_SPI_CLK_ON_REQ: u8 = 0x41;
 const PWM3610_SPI_PAGE0: u8 = 0x7f;
@@ -106,6 +108,16 @@ const RUN_DOWNSHIFT_INIT: u8 = 0x04;
 const REST1_RATE_INIT: u8 = 0x04;
 const REST1_DOWNSHIFT_INIT: u8 = 0x0f;
 
+// ============================================================================
+// Power-tuning register step sizes (datasheet timing resolution)
+// ============================================================================
+/// Each run-downshift count is roughly one run-mode frame period.
+const RUN_DOWNSHIFT_STEP_MS: u32 = 8;
+/// Each rest1-rate count is one fixed sample-interval step.
+const REST1_RATE_STEP_MS: u32 = 1;
+/// Each rest1-downshift count is one fixed sample-interval step.
+const REST1_DOWNSHIFT_STEP_MS: u32 = 32;
+
 // ============================================================================
 // Constants
 // ============================================================================
@@ -123,9 +135,7 @@ const PERFORMANCE_FMODE_MASK: u8 = 0x0f << 4;
 const PERFORMANCE_FMODE_NORMAL: u8 = 0x00 << 4;
 const PERFORMANCE_FMODE_FORCE_AWAKE: u8 = 0x0f << 4;
 const POWER_UP_RESET_VAL: u8 = 0x5a;
-#[allow(dead_code)]
 const POWER_UP_WAKEUP: u8 = 0x96;
-#[allow(dead_code)]
 const SHUTDOWN_ENABLE: u8 = 0xe7;
 const SPI_PAGE0_1: u8 = 0xff;
 const SPI_PAGE1_0: u8 = 0x00;
@@ -168,6 +178,19 @@ pub struct Pmw3610Config {
     pub force_awake: bool,
     /// Enable smart mode for better tracking on shiny surfaces
     pub smart_mode: bool,
+    /// Run-mode to rest1-mode downshift time in milliseconds. Set to -1 to
+    /// use the sensor default.
+    pub run_downshift_ms: i32,
+    /// Rest1-mode motion sample period in milliseconds. Set to -1 to use the
+    /// sensor default.
+    pub rest1_sample_period_ms: i32,
+    /// Rest1-mode to rest2-mode downshift time in milliseconds. Set to -1 to
+    /// use the sensor default.
+    pub rest1_downshift_ms: i32,
+    /// Suppress motion reports when SQUAL drops below this value (sensor
+    /// lifted off the surface). `None` disables lift-off detection and keeps
+    /// the normal-mode burst read length.
+    pub lift_threshold: Option<u8>,
 }
 
 impl Default for Pmw3610Config {
@@ -179,6 +202,10 @@ impl Default for Pmw3610Config {
             swap_xy: false,
             force_awake: false,
             smart_mode: false,
+            run_downshift_ms: -1,
+            rest1_sample_period_ms: -1,
+            rest1_downshift_ms: -1,
+            lift_threshold: None,
         }
     }
 }
@@ -195,6 +222,16 @@ pub enum Pmw3610Error {
     InitFailed,
     /// Invalid CPI value
     InvalidCpi,
+    /// A power-tuning timing value is out of the sensor's representable range
+    InvalidTiming,
+}
+
+/// Runtime power state of the sensor, mirroring the Zephyr `pm_device`
+/// suspended/active model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Format)]
+pub enum PowerState {
+    Active,
+    Suspended,
 }
 
 /// Motion data from the sensor
@@ -202,70 +239,58 @@ pub enum Pmw3610Error {
 pub struct MotionData {
     pub dx: i16,
     pub dy: i16,
+    /// Surface quality count, only populated when smart mode or
+    /// [`Pmw3610Config::lift_threshold`] is enabled; 0 otherwise.
+    pub squal: u8,
+    /// Shutter (exposure) value backing `squal`, same availability as above.
+    pub shutter: u16,
+    /// True if `squal` dropped below `lift_threshold`, i.e. the sensor has
+    /// been lifted off the surface. `dx`/`dy` are suppressed to 0 when set.
+    pub lifted: bool,
 }
 
-/// PMW3610 driver using half-duplex bit-banged SPI
-///
-/// The PMW3610 uses a bidirectional SDIO line for SPI communication.
-/// This driver implements bit-banging to support this half-duplex mode.
-///
-/// # Type Parameters
-/// - `SCK`: SPI clock pin (output)
-/// - `SDIO`: Bidirectional data pin
-/// - `CS`: Chip select pin (active low)
-/// - `MOTION`: Optional motion interrupt pin (active low)
-pub struct Pmw3610<SCK, SDIO, CS, MOTION>
-where
-    SCK: OutputPin,
-    SDIO: BidirectionalPin,
-    CS: OutputPin,
-    MOTION: InputPin,
-{
+/// Byte-level transport for the PMW3610's single SPI-like link: a register
+/// access is `begin()` (assert CS), alternating `write_byte`/`read_byte`
+/// calls, then `end()` (release CS). [`BitBangTransport`] implements this
+/// over a bidirectional data pin; a hardware half-duplex SPI peripheral can
+/// implement it directly for a much faster, CPU-free link.
+pub trait Pmw3610Transport {
+    /// Assert CS and wait out the CS-to-clock setup time.
+    async fn begin(&mut self);
+
+    /// Release CS at the end of a register access.
+    async fn end(&mut self);
+
+    /// Write one byte, MSB first.
+    async fn write_byte(&mut self, byte: u8);
+
+    /// Read one byte, MSB first.
+    async fn read_byte(&mut self) -> u8;
+
+    /// Drive the link to its idle state (CS high) outside of a register
+    /// access, e.g. right after power-up or before a shutdown write.
+    async fn idle(&mut self);
+}
+
+/// Bit-banged half-duplex transport over a clock pin, a bidirectional data
+/// pin, and a chip-select pin. This is the original PMW3610 driver transport,
+/// needed for MCUs with no spare hardware 3-wire SPI peripheral.
+pub struct BitBangTransport<SCK, SDIO, CS> {
     sck: SCK,
     sdio: SDIO,
     cs: CS,
-    motion_gpio: Option<MOTION>,
-    config: Pmw3610Config,
-    smart_flag: bool,
 }
 
-impl<SCK, SDIO, CS, MOTION> Pmw3610<SCK, SDIO, CS, MOTION>
+impl<SCK, SDIO, CS> BitBangTransport<SCK, SDIO, CS>
 where
     SCK: OutputPin,
     SDIO: BidirectionalPin,
     CS: OutputPin,
-    MOTION: InputPin,
 {
-    /// Create a new PMW3610 driver instance
-    pub fn new(
-        sck: SCK,
-        sdio: SDIO,
-        cs: CS,
-        motion_gpio: Option<MOTION>,
-        config: Pmw3610Config,
-    ) -> Self {
-        Self {
-            sck,
-            sdio,
-            cs,
-            motion_gpio,
-            config,
-            smart_flag: false,
-        }
+    pub fn new(sck: SCK, sdio: SDIO, cs: CS) -> Self {
+        Self { sck, sdio, cs }
     }
 
-    /// Check if motion is pending (motion GPIO is active low)
-    pub fn motion_pending(&mut self) -> bool {
-        match &mut self.motion_gpio {
-            Some(gpio) => gpio.is_low().unwrap_or(true),
-            None => true,
-        }
-    }
-
-    // ========================================================================
-    // Low-level SPI bit-banging
-    // ========================================================================
-
     #[inline(always)]
     fn spi_delay() {
         // Short busy-wait delay for SPI timing
@@ -282,9 +307,25 @@ where
             core::hint::spin_loop();
         }
     }
+}
+
+impl<SCK, SDIO, CS> Pmw3610Transport for BitBangTransport<SCK, SDIO, CS>
+where
+    SCK: OutputPin,
+    SDIO: BidirectionalPin,
+    CS: OutputPin,
+{
+    async fn begin(&mut self) {
+        let _ = self.cs.set_low();
+        Timer::after(Duration::from_micros(T_NCS_SCLK_US)).await;
+    }
+
+    async fn end(&mut self) {
+        Self::short_delay();
+        let _ = self.cs.set_high();
+    }
 
-    /// Write a byte over the bidirectional SPI (MSB first)
-    fn write_byte(&mut self, byte: u8) {
+    async fn write_byte(&mut self, byte: u8) {
         self.sdio.set_as_output();
 
         for i in (0..8).rev() {
@@ -303,8 +344,7 @@ where
         }
     }
 
-    /// Read a byte from the bidirectional SPI (MSB first)
-    fn read_byte(&mut self) -> u8 {
+    async fn read_byte(&mut self) -> u8 {
         self.sdio.set_as_input();
 
         let mut byte = 0u8;
@@ -324,24 +364,128 @@ where
         byte
     }
 
+    async fn idle(&mut self) {
+        let _ = self.cs.set_high();
+        let _ = self.sck.set_high();
+    }
+}
+
+/// Hardware 3-wire/half-duplex `SpiBus` backed transport. CS is still driven
+/// directly here (rather than via an `embedded-hal-bus` `SpiDevice`) since
+/// these HAL peripherals expose a bare bus with no CS management of their
+/// own, the same division of responsibility as [`BitBangTransport`].
+pub struct SpiBusTransport<SPI, CS> {
+    spi: SPI,
+    cs: CS,
+}
+
+impl<SPI, CS> SpiBusTransport<SPI, CS>
+where
+    SPI: embedded_hal::spi::SpiBus,
+    CS: OutputPin,
+{
+    pub fn new(spi: SPI, cs: CS) -> Self {
+        Self { spi, cs }
+    }
+}
+
+impl<SPI, CS> Pmw3610Transport for SpiBusTransport<SPI, CS>
+where
+    SPI: embedded_hal::spi::SpiBus,
+    CS: OutputPin,
+{
+    async fn begin(&mut self) {
+        let _ = self.cs.set_low();
+        Timer::after(Duration::from_micros(T_NCS_SCLK_US)).await;
+    }
+
+    async fn end(&mut self) {
+        let _ = self.cs.set_high();
+    }
+
+    async fn write_byte(&mut self, byte: u8) {
+        let _ = self.spi.write(&[byte]);
+    }
+
+    async fn read_byte(&mut self) -> u8 {
+        let mut buf = [0u8];
+        let _ = self.spi.read(&mut buf);
+        buf[0]
+    }
+
+    async fn idle(&mut self) {
+        let _ = self.cs.set_high();
+    }
+}
+
+/// PMW3610 driver, generic over its byte transport
+///
+/// # Type Parameters
+/// - `T`: byte transport, see [`Pmw3610Transport`]
+/// - `MOTION`: Optional motion interrupt pin (active low)
+pub struct Pmw3610<T, MOTION>
+where
+    T: Pmw3610Transport,
+    MOTION: InputPin,
+{
+    transport: T,
+    motion_gpio: Option<MOTION>,
+    config: Pmw3610Config,
+    smart_flag: bool,
+    power_state: PowerState,
+}
+
+impl<T, MOTION> Pmw3610<T, MOTION>
+where
+    T: Pmw3610Transport,
+    MOTION: InputPin,
+{
+    /// Create a new PMW3610 driver instance
+    pub fn new(transport: T, motion_gpio: Option<MOTION>, config: Pmw3610Config) -> Self {
+        Self {
+            transport,
+            motion_gpio,
+            config,
+            smart_flag: false,
+            power_state: PowerState::Active,
+        }
+    }
+
+    /// Current power state of the sensor.
+    pub fn power_state(&self) -> PowerState {
+        self.power_state
+    }
+
+    /// Check if motion is pending (motion GPIO is active low)
+    pub fn motion_pending(&mut self) -> bool {
+        match &mut self.motion_gpio {
+            Some(gpio) => gpio.is_low().unwrap_or(true),
+            None => true,
+        }
+    }
+
+    /// True if a motion interrupt pin was wired, i.e. the caller can sleep
+    /// on [`wait_for_motion`](Self::wait_for_motion) instead of polling.
+    pub fn has_motion_pin(&self) -> bool {
+        self.motion_gpio.is_some()
+    }
+
     // ========================================================================
     // Register access functions
     // ========================================================================
 
     /// Read a single byte from a register
     async fn read_reg(&mut self, addr: u8) -> Result<u8, Pmw3610Error> {
-        let _ = self.cs.set_low();
-        Timer::after(Duration::from_micros(T_NCS_SCLK_US)).await;
+        self.transport.begin().await;
 
         // Send address with read bit (bit 7 = 0)
-        self.write_byte(addr & 0x7f);
+        self.transport.write_byte(addr & 0x7f).await;
 
         Timer::after(Duration::from_micros(T_SRAD_US)).await;
 
-        let value = self.read_byte();
+        let value = self.transport.read_byte().await;
 
-        Self::short_delay();
-        let _ = self.cs.set_high();
+        self.transport.end().await;
 
         Timer::after(Duration::from_micros(T_SRX_US)).await;
 
@@ -350,21 +494,18 @@ where
 
     /// Read multiple bytes using burst read
     async fn read_burst(&mut self, addr: u8, data: &mut [u8]) -> Result<(), Pmw3610Error> {
-        let _ = self.cs.set_low();
-        Timer::after(Duration::from_micros(T_NCS_SCLK_US)).await;
+        self.transport.begin().await;
 
         // Send address with read bit (bit 7 = 0)
-        self.write_byte(addr & 0x7f);
+        self.transport.write_byte(addr & 0x7f).await;
 
         Timer::after(Duration::from_micros(T_SRAD_US)).await;
 
         for byte in data.iter_mut() {
-            *byte = self.read_byte();
-            Self::spi_delay();
+            *byte = self.transport.read_byte().await;
         }
 
-        Self::short_delay();
-        let _ = self.cs.set_high();
+        self.transport.end().await;
 
         Timer::after(Duration::from_micros(T_BEXIT_US)).await;
 
@@ -373,16 +514,15 @@ where
 
     /// Write a single byte to a register
     async fn write_reg(&mut self, addr: u8, value: u8) -> Result<(), Pmw3610Error> {
-        let _ = self.cs.set_low();
-        Timer::after(Duration::from_micros(T_NCS_SCLK_US)).await;
+        self.transport.begin().await;
 
         // Send address with write bit (bit 7 = 1)
-        self.write_byte(addr | SPI_WRITE);
+        self.transport.write_byte(addr | SPI_WRITE).await;
 
-        self.write_byte(value);
+        self.transport.write_byte(value).await;
 
         Timer::after(Duration::from_micros(T_SCLK_NCS_WR_US)).await;
-        let _ = self.cs.set_high();
+        self.transport.end().await;
 
         Timer::after(Duration::from_micros(T_SWX_US)).await;
 
@@ -409,6 +549,24 @@ where
     // Sensor configuration
     // ========================================================================
 
+    /// Convert a millisecond timing value to its register encoding, where
+    /// each register count represents `step_ms` of real time. `ms < 0` is the
+    /// "use the sensor default" sentinel and returns `default_reg` unchanged;
+    /// any other value that doesn't round to a representable 1-255 count is
+    /// rejected rather than silently clamped.
+    fn timing_reg(ms: i32, step_ms: u32, default_reg: u8) -> Result<u8, Pmw3610Error> {
+        if ms < 0 {
+            return Ok(default_reg);
+        }
+
+        let counts = (ms as u32 + step_ms / 2) / step_ms;
+        if !(1..=255).contains(&counts) {
+            return Err(Pmw3610Error::InvalidTiming);
+        }
+
+        Ok(counts as u8)
+    }
+
     /// Set sensor resolution in CPI (200-3200, step 200)
     pub async fn set_resolution(&mut self, cpi: u16) -> Result<(), Pmw3610Error> {
         if !(RES_MIN..=RES_MAX).contains(&cpi) {
@@ -486,14 +644,45 @@ where
         }
 
         self.write_reg(PMW3610_PERFORMANCE, PERFORMANCE_INIT).await?;
-        self.write_reg(PMW3610_RUN_DOWNSHIFT, RUN_DOWNSHIFT_INIT)
+
+        let run_downshift_reg = Self::timing_reg(
+            self.config.run_downshift_ms,
+            RUN_DOWNSHIFT_STEP_MS,
+            RUN_DOWNSHIFT_INIT,
+        )?;
+        self.write_reg(PMW3610_RUN_DOWNSHIFT, run_downshift_reg)
             .await?;
-        self.write_reg(PMW3610_REST1_RATE, REST1_RATE_INIT).await?;
-        self.write_reg(PMW3610_REST1_DOWNSHIFT, REST1_DOWNSHIFT_INIT)
+
+        let rest1_rate_reg = Self::timing_reg(
+            self.config.rest1_sample_period_ms,
+            REST1_RATE_STEP_MS,
+            REST1_RATE_INIT,
+        )?;
+        self.write_reg(PMW3610_REST1_RATE, rest1_rate_reg).await?;
+
+        let rest1_downshift_reg = Self::timing_reg(
+            self.config.rest1_downshift_ms,
+            REST1_DOWNSHIFT_STEP_MS,
+            REST1_DOWNSHIFT_INIT,
+        )?;
+        self.write_reg(PMW3610_REST1_DOWNSHIFT, rest1_downshift_reg)
             .await?;
 
-        // Configuration: axis inversion
+        self.spi_clk_off().await?;
+
+        self.restore_config().await?;
+
+        info!("PMW3610 initialized successfully");
+        Ok(())
+    }
+
+    /// Re-apply the cached [`Pmw3610Config`] (axis inversion, resolution,
+    /// force-awake) to the sensor. Used both by first-time `configure()` and
+    /// by `resume()` after a suspend, since the sensor forgets its settings
+    /// across a shutdown/wakeup cycle.
+    async fn restore_config(&mut self) -> Result<(), Pmw3610Error> {
         if self.config.invert_x || self.config.invert_y {
+            self.spi_clk_on().await?;
             self.write_reg(PWM3610_SPI_PAGE0, SPI_PAGE0_1).await?;
 
             let mut val = self.read_reg(PMW3610_RES_STEP).await?;
@@ -512,10 +701,9 @@ where
 
             self.write_reg(PMW3610_RES_STEP, val).await?;
             self.write_reg(PWM3610_SPI_PAGE1, SPI_PAGE1_0).await?;
+            self.spi_clk_off().await?;
         }
 
-        self.spi_clk_off().await?;
-
         // The remaining functions call spi_clk_on/off independently.
 
         if self.config.res_cpi > 0 {
@@ -524,27 +712,64 @@ where
 
         self.force_awake(self.config.force_awake).await?;
 
-        info!("PMW3610 initialized successfully");
         Ok(())
     }
 
     /// Initialize the sensor (public API)
     pub async fn init(&mut self) -> Result<(), Pmw3610Error> {
-        // Set initial pin states
-        let _ = self.cs.set_high();
-        let _ = self.sck.set_high();
+        self.transport.idle().await;
         Timer::after(Duration::from_millis(1)).await;
 
         self.configure().await
     }
 
+    /// Put the sensor into its lowest-power shutdown state. Releases the SPI
+    /// clock and drops CS afterwards so the bus is idle while suspended.
+    pub async fn suspend(&mut self) -> Result<(), Pmw3610Error> {
+        if self.power_state == PowerState::Suspended {
+            return Ok(());
+        }
+
+        self.spi_clk_on().await?;
+        self.write_reg(PMW3610_SHUTDOWN, SHUTDOWN_ENABLE).await?;
+        self.spi_clk_off().await?;
+        self.transport.idle().await;
+
+        self.power_state = PowerState::Suspended;
+        info!("PMW3610 suspended");
+        Ok(())
+    }
+
+    /// Wake the sensor from [`suspend`](Self::suspend) and restore the cached
+    /// configuration, without re-running the full product-ID-gated
+    /// `configure()` path.
+    pub async fn resume(&mut self) -> Result<(), Pmw3610Error> {
+        if self.power_state == PowerState::Active {
+            return Ok(());
+        }
+
+        self.transport.idle().await;
+        self.write_reg(PMW3610_POWER_UP_RESET, POWER_UP_WAKEUP)
+            .await?;
+        Timer::after(Duration::from_millis(RESET_DELAY_MS)).await;
+
+        self.restore_config().await?;
+
+        self.power_state = PowerState::Active;
+        info!("PMW3610 resumed");
+        Ok(())
+    }
+
     // ========================================================================
     // Motion reading
     // ========================================================================
 
     /// Read motion data from the sensor (motion work handler)
     pub async fn read_motion(&mut self) -> Result<MotionData, Pmw3610Error> {
-        let burst_data_len = if self.config.smart_mode {
+        // SQUAL/shutter only live past the normal-mode burst length, so only
+        // read the longer burst when something actually consumes them.
+        let extended_burst = self.config.smart_mode || self.config.lift_threshold.is_some();
+        let burst_data_len = if extended_burst {
             BURST_DATA_LEN_SMART
         } else {
             BURST_DATA_LEN_NORMAL
@@ -567,11 +792,15 @@ where
         let dx = Self::sign_extend(x, PMW3610_DATA_SIZE_BITS - 1);
         let dy = Self::sign_extend(y, PMW3610_DATA_SIZE_BITS - 1);
 
-        // Smart mode handling
-        if self.config.smart_mode {
-            let shutter_val = ((burst_data[BURST_SHUTTER_HI] as u16) << 8)
+        let (mut squal, mut shutter_val) = (0u8, 0u16);
+        if extended_burst {
+            squal = burst_data[BURST_SQUAL];
+            shutter_val = ((burst_data[BURST_SHUTTER_HI] as u16) << 8)
                 | (burst_data[BURST_SHUTTER_LO] as u16);
+        }
 
+        // Smart mode handling
+        if self.config.smart_mode {
             if self.smart_flag && shutter_val < SHUTTER_SMART_THRESHOLD {
                 self.spi_clk_on().await?;
                 self.write_reg(PMW3610_SMART_MODE, SMART_MODE_ENABLE)
@@ -593,7 +822,19 @@ where
             (dx, dy)
         };
 
-        Ok(MotionData { dx, dy })
+        let lifted = self
+            .config
+            .lift_threshold
+            .is_some_and(|threshold| squal < threshold);
+        let (dx, dy) = if lifted { (0, 0) } else { (dx, dy) };
+
+        Ok(MotionData {
+            dx,
+            dy,
+            squal,
+            shutter: shutter_val,
+            lifted,
+        })
     }
 
     /// Sign extend a value (equivalent to Zephyr's sign_extend)
@@ -607,6 +848,24 @@ where
     }
 }
 
+impl<T, MOTION> Pmw3610<T, MOTION>
+where
+    T: Pmw3610Transport,
+    MOTION: InputPin + embedded_hal_async::digital::Wait,
+{
+    /// Sleep until the motion pin (active-low) signals new data instead of
+    /// polling `motion_pending()` on a fixed interval, letting the core enter
+    /// deep sleep between movements. A no-op when no motion pin is wired.
+    pub async fn wait_for_motion(&mut self) -> Result<(), Pmw3610Error> {
+        if let Some(gpio) = &mut self.motion_gpio {
+            gpio.wait_for_falling_edge()
+                .await
+                .map_err(|_| Pmw3610Error::Spi)?;
+        }
+        Ok(())
+    }
+}
+
 // ============================================================================
 // RMK InputDevice implementation (optional, enabled with "rmk" feature)
 // ============================================================================
@@ -629,47 +888,100 @@ mod rmk_integration {
         Failed,
     }
 
+    /// How `Pmw3610Device` maps sensor motion onto the outgoing `MouseReport`:
+    /// as cursor movement, or as scroll wheel/pan (for a scroll layer on a
+    /// trackball keyboard). Switched at runtime via
+    /// [`Pmw3610Device::set_mode`].
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Format)]
+    pub enum ReportMode {
+        Cursor,
+        Scroll,
+    }
+
     /// PMW3610 as an InputDevice for RMK
-    pub struct Pmw3610Device<SCK, SDIO, CS, MOTION>
+    pub struct Pmw3610Device<T, MOTION>
     where
-        SCK: OutputPin,
-        SDIO: BidirectionalPin,
-        CS: OutputPin,
-        MOTION: InputPin,
+        T: Pmw3610Transport,
+        MOTION: InputPin + embedded_hal_async::digital::Wait,
     {
-        sensor: Pmw3610<SCK, SDIO, CS, MOTION>,
+        sensor: Pmw3610<T, MOTION>,
         init_state: InitState,
         poll_interval: Duration,
+        /// Signed motion not yet emitted because it overflowed the i8
+        /// `MouseReport` clamp on a previous poll (`Cursor` mode), or not yet
+        /// enough to cross a scroll detent (`Scroll` mode); drained on later
+        /// polls so fast swipes aren't truncated and scrolling isn't
+        /// hypersensitive at high CPI.
+        accum_dx: i32,
+        accum_dy: i32,
+        /// Set only when `accum_dx`/`accum_dy` hold `Cursor`-mode overflow
+        /// residue, never for `Scroll` mode's routine sub-detent residue —
+        /// `read_event` races `wait_for_motion()` against `poll_interval`
+        /// only while this is set, so `Scroll` mode (which is nonzero here
+        /// almost all the time) isn't kept off the pure interrupt-driven
+        /// wait `wait_for_motion` exists for.
+        overflow_pending: bool,
+        mode: ReportMode,
+        /// Sensor counts per scroll detent in `Scroll` mode; ignored in
+        /// `Cursor` mode.
+        scroll_counts_per_detent: i32,
     }
 
-    impl<SCK, SDIO, CS, MOTION> Pmw3610Device<SCK, SDIO, CS, MOTION>
+    impl<T, MOTION> Pmw3610Device<T, MOTION>
     where
-        SCK: OutputPin,
-        SDIO: BidirectionalPin,
-        CS: OutputPin,
-        MOTION: InputPin,
+        T: Pmw3610Transport,
+        MOTION: InputPin + embedded_hal_async::digital::Wait,
     {
         const MAX_INIT_RETRIES: u8 = 3;
-
-        pub fn new(
-            sck: SCK,
-            sdio: SDIO,
-            cs: CS,
-            motion_gpio: Option<MOTION>,
-            config: Pmw3610Config,
-        ) -> Self {
+        /// Default sensor counts per scroll detent; tuned so a typical swipe
+        /// at the default 1200 CPI produces a handful of wheel ticks rather
+        /// than dozens.
+        const DEFAULT_SCROLL_COUNTS_PER_DETENT: i32 = 20;
+        /// How often to re-check for resume while the sensor is suspended.
+        /// There's no wake-on-motion while suspended (the sensor itself is
+        /// powered down), so this just bounds how long a `resume()` call
+        /// from elsewhere takes to be noticed; it's seconds, not the
+        /// microsecond-scale `poll_interval`, so suspend actually saves
+        /// power instead of busy-looping at the full poll rate.
+        const SUSPENDED_RECHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+        pub fn new(transport: T, motion_gpio: Option<MOTION>, config: Pmw3610Config) -> Self {
             Self {
-                sensor: Pmw3610::new(sck, sdio, cs, motion_gpio, config),
+                sensor: Pmw3610::new(transport, motion_gpio, config),
                 init_state: InitState::Pending,
                 poll_interval: Duration::from_micros(500),
+                accum_dx: 0,
+                accum_dy: 0,
+                overflow_pending: false,
+                mode: ReportMode::Cursor,
+                scroll_counts_per_detent: Self::DEFAULT_SCROLL_COUNTS_PER_DETENT,
             }
         }
 
+        /// Switch between cursor and scroll reporting, e.g. from a keymap
+        /// layer toggle. Resets the accumulator so a pending cursor swipe
+        /// doesn't leak into the first scroll report (or vice versa).
+        pub fn set_mode(&mut self, mode: ReportMode) {
+            self.accum_dx = 0;
+            self.accum_dy = 0;
+            self.overflow_pending = false;
+            self.mode = mode;
+        }
+
+        /// Override how many sensor counts make up one scroll detent in
+        /// `Scroll` mode.
+        pub fn set_scroll_counts_per_detent(&mut self, counts: i32) {
+            self.scroll_counts_per_detent = counts;
+        }
+
         async fn try_init(&mut self) -> bool {
             match self.init_state {
                 InitState::Ready => return true,
                 InitState::Failed => return false,
                 InitState::Pending => {
+                    self.accum_dx = 0;
+                    self.accum_dy = 0;
+                    self.overflow_pending = false;
                     self.init_state = InitState::Initializing(0);
                 }
                 InitState::Initializing(_) => {}
@@ -703,45 +1015,140 @@ mod rmk_integration {
 
             false
         }
+
+        /// Idle the sensor on USB suspend / keyboard sleep. A no-op if the
+        /// sensor hasn't finished initializing yet.
+        pub async fn suspend(&mut self) -> Result<(), Pmw3610Error> {
+            self.accum_dx = 0;
+            self.accum_dy = 0;
+            self.overflow_pending = false;
+            if self.init_state != InitState::Ready {
+                return Ok(());
+            }
+            self.sensor.suspend().await
+        }
+
+        /// Bring the sensor back from [`suspend`](Self::suspend) without a
+        /// full re-init.
+        pub async fn resume(&mut self) -> Result<(), Pmw3610Error> {
+            self.accum_dx = 0;
+            self.accum_dy = 0;
+            self.overflow_pending = false;
+            if self.init_state != InitState::Ready {
+                return Ok(());
+            }
+            self.sensor.resume().await
+        }
     }
 
-    impl<SCK, SDIO, CS, MOTION> InputDevice for Pmw3610Device<SCK, SDIO, CS, MOTION>
+    impl<T, MOTION> InputDevice for Pmw3610Device<T, MOTION>
     where
-        SCK: OutputPin,
-        SDIO: BidirectionalPin,
-        CS: OutputPin,
-        MOTION: InputPin,
+        T: Pmw3610Transport,
+        MOTION: InputPin + embedded_hal_async::digital::Wait,
     {
         async fn read_event(&mut self) -> Event {
             loop {
-                // Wait for polling interval
-                Timer::after(self.poll_interval).await;
-
                 // Try to initialize if not ready yet
                 if self.init_state != InitState::Ready {
+                    Timer::after(self.poll_interval).await;
                     if !self.try_init().await {
                         continue;
                     }
                 }
 
-                // Only read if motion is pending (motion GPIO low) or no motion GPIO configured
-                if !self.sensor.motion_pending() {
+                if self.sensor.power_state() == PowerState::Suspended {
+                    Timer::after(Self::SUSPENDED_RECHECK_INTERVAL).await;
                     continue;
                 }
 
+                // Only `Cursor` mode's overflow residue needs the bounded
+                // race below: `Scroll` mode routinely sits nonzero below one
+                // detent between polls (that's the point of the divisor), so
+                // gating the race on the raw accumulator instead would keep
+                // a Scroll-mode board on the timer-raced path forever once
+                // it's seen any motion, defeating `wait_for_motion`'s whole
+                // point of sleeping until the next edge.
+                let residual_pending = self.overflow_pending;
+
+                if self.sensor.has_motion_pin() {
+                    // Sleep until the motion pin signals data instead of
+                    // burning CPU on a fixed poll interval. But a swipe that
+                    // overflowed the report on a previous poll can leave the
+                    // accumulator nonzero with no new edge ever coming (the
+                    // ball already stopped) — race the wait against the poll
+                    // interval in that case so the residual still gets
+                    // drained instead of blocking on `wait_for_motion`
+                    // forever.
+                    if residual_pending {
+                        match select(self.sensor.wait_for_motion(), Timer::after(self.poll_interval)).await {
+                            Either::First(Err(_)) => {
+                                Timer::after(self.poll_interval).await;
+                                continue;
+                            }
+                            _ => {}
+                        }
+                    } else if self.sensor.wait_for_motion().await.is_err() {
+                        Timer::after(self.poll_interval).await;
+                        continue;
+                    }
+                } else {
+                    // No interrupt pin wired; fall back to polling.
+                    Timer::after(self.poll_interval).await;
+                    if !self.sensor.motion_pending() && !residual_pending {
+                        continue;
+                    }
+                }
+
                 match self.sensor.read_motion().await {
                     Ok(motion) => {
-                        if motion.dx != 0 || motion.dy != 0 {
-                            // Clamp values to i8 range for mouse report
-                            let x = motion.dx.clamp(-127, 127) as i8;
-                            let y = motion.dy.clamp(-127, 127) as i8;
+                        self.accum_dx += motion.dx as i32;
+                        self.accum_dy += motion.dy as i32;
+
+                        // A zero-motion poll can still have a nonzero
+                        // accumulator left over from a swipe that overflowed
+                        // the i8 report on a previous poll; keep draining it.
+                        // `Scroll` mode has a different invariant though: the
+                        // accumulator routinely sits nonzero below one detent
+                        // for many polls in a row (that's the point of the
+                        // divisor), so it's gated separately below on an
+                        // actual detent crossing instead of on the raw
+                        // accumulator, or a slow swipe would flood the HID
+                        // channel with no-op zero reports every poll.
+                        if self.accum_dx != 0 || self.accum_dy != 0 {
+                            let (x, y, wheel, pan) = match self.mode {
+                                ReportMode::Cursor => {
+                                    let x = self.accum_dx.clamp(-127, 127);
+                                    let y = self.accum_dy.clamp(-127, 127);
+                                    self.accum_dx -= x;
+                                    self.accum_dy -= y;
+                                    self.overflow_pending = self.accum_dx != 0 || self.accum_dy != 0;
+                                    (x, y, 0, 0)
+                                }
+                                ReportMode::Scroll => {
+                                    // Sub-detent residue left below is
+                                    // intentional, not overflow — never
+                                    // race-worthy.
+                                    self.overflow_pending = false;
+                                    let divisor = self.scroll_counts_per_detent.max(1);
+                                    let pan_detents =
+                                        (self.accum_dx / divisor).clamp(-127, 127);
+                                    let wheel_detents =
+                                        (self.accum_dy / divisor).clamp(-127, 127);
+                                    self.accum_dx -= pan_detents * divisor;
+                                    self.accum_dy -= wheel_detents * divisor;
+                                    if wheel_detents == 0 && pan_detents == 0 {
+                                        continue;
+                                    }
+                                    (0, 0, wheel_detents, pan_detents)
+                                }
+                            };
 
                             let mouse_report = MouseReport {
                                 buttons: 0,
-                                x,
-                                y,
-                                wheel: 0,
-                                pan: 0,
+                                x: x as i8,
+                                y: y as i8,
+                                wheel: wheel as i8,
+                                pan: pan as i8,
                             };
 
                             // Send mouse report directly
@@ -749,17 +1156,28 @@ mod rmk_integration {
                                 .send(Report::MouseReport(mouse_report))
                                 .await;
 
+                            // `Cursor` mode's x/y are already the right pair
+                            // for the axis event, but `Scroll` mode sends its
+                            // motion through `wheel`/`pan` instead (a
+                            // `Joystick` consumer has no wheel/pan axis of its
+                            // own), so route those into X/Y there instead of
+                            // the literal zeros `x`/`y` hold in that mode.
+                            let (axis_x, axis_y) = match self.mode {
+                                ReportMode::Cursor => (x, y),
+                                ReportMode::Scroll => (pan, wheel),
+                            };
+
                             // Return joystick event for compatibility with processor chain
                             return Event::Joystick([
                                 AxisEvent {
                                     typ: AxisValType::Rel,
                                     axis: Axis::X,
-                                    value: motion.dx,
+                                    value: axis_x as i16,
                                 },
                                 AxisEvent {
                                     typ: AxisValType::Rel,
                                     axis: Axis::Y,
-                                    value: motion.dy,
+                                    value: axis_y as i16,
                                 },
                                 AxisEvent {
                                     typ: AxisValType::Rel,
@@ -779,7 +1197,66 @@ mod rmk_integration {
 }
 
 #[cfg(feature = "rmk")]
-pub use rmk_integration::Pmw3610Device;
+pub use rmk_integration::{Pmw3610Device, ReportMode};
+
+// ============================================================================
+// Generic embedded-hal 1.0 implementation
+// ============================================================================
+
+/// Mode-switch capability for a pin that can flip between push-pull output
+/// and floating input. embedded-hal 1.0 deliberately doesn't define this
+/// (each HAL's flex-pin type owns its own API for it), so a HAL's flex-pin
+/// type implements this small trait once to pick up [`BidirectionalPin`]
+/// below instead of a hand-written `impl` block per HAL.
+#[cfg(feature = "generic-digital")]
+pub trait PinModeSwitch {
+    fn set_as_output(&mut self);
+    fn set_as_input(&mut self);
+}
+
+/// Adapts any embedded-hal 1.0 `InputPin + OutputPin + PinModeSwitch` pin
+/// into a [`BidirectionalPin`], so the PMW3610 bit-banged 3-wire interface
+/// works on RP2040, STM32, and other HALs without a new per-HAL `impl` block
+/// (compare the hand-written `embassy-nrf` block below). A newtype rather
+/// than a blanket impl over `P` directly, for two reasons: it avoids a
+/// coherence conflict with the per-HAL impls below if a board enables both
+/// features, and it bridges embedded-hal 1.0's `InputPin::is_high(&mut self)`
+/// to `BidirectionalPin::is_high(&self)` via a `RefCell`.
+#[cfg(feature = "generic-digital")]
+pub struct GenericBidirectionalPin<P>(core::cell::RefCell<P>);
+
+#[cfg(feature = "generic-digital")]
+impl<P> GenericBidirectionalPin<P> {
+    pub fn new(pin: P) -> Self {
+        Self(core::cell::RefCell::new(pin))
+    }
+}
+
+#[cfg(feature = "generic-digital")]
+impl<P> BidirectionalPin for GenericBidirectionalPin<P>
+where
+    P: InputPin + OutputPin + PinModeSwitch,
+{
+    fn set_as_output(&mut self) {
+        self.0.get_mut().set_as_output();
+    }
+
+    fn set_as_input(&mut self) {
+        self.0.get_mut().set_as_input();
+    }
+
+    fn set_high(&mut self) {
+        let _ = self.0.get_mut().set_high();
+    }
+
+    fn set_low(&mut self) {
+        let _ = self.0.get_mut().set_low();
+    }
+
+    fn is_high(&self) -> bool {
+        self.0.borrow_mut().is_high().unwrap_or(false)
+    }
+}
 
 // ============================================================================
 // HAL-specific implementations
@@ -808,3 +1285,356 @@ impl<'d> BidirectionalPin for embassy_nrf::gpio::Flex<'d> {
         embassy_nrf::gpio::Flex::is_high(self)
     }
 }
+
+/// Embassy-RP implementation of BidirectionalPin for Flex pin. RP2040 GPIOs
+/// are 3.3V-only (not 5V tolerant like some nRF pins), so boards must level
+/// shift the SDIO line if the sensor side runs at a different voltage; this
+/// impl only covers drive/pull configuration, not voltage translation.
+#[cfg(feature = "embassy-rp")]
+impl<'d> BidirectionalPin for embassy_rp::gpio::Flex<'d> {
+    fn set_as_output(&mut self) {
+        embassy_rp::gpio::Flex::set_drive_strength(self, embassy_rp::gpio::Drive::_2mA);
+        embassy_rp::gpio::Flex::set_as_output(self);
+    }
+
+    fn set_as_input(&mut self) {
+        embassy_rp::gpio::Flex::set_pull(self, embassy_rp::gpio::Pull::None);
+        embassy_rp::gpio::Flex::set_as_input(self);
+    }
+
+    fn set_high(&mut self) {
+        embassy_rp::gpio::Flex::set_high(self);
+    }
+
+    fn set_low(&mut self) {
+        embassy_rp::gpio::Flex::set_low(self);
+    }
+
+    fn is_high(&self) -> bool {
+        embassy_rp::gpio::Flex::is_high(self)
+    }
+}
+
+// ============================================================================
+// I2C GPIO expander implementation
+// ============================================================================
+
+/// Cached direction and output registers for an [`Aw9523Expander`].
+#[cfg(feature = "gpio-expander")]
+struct Aw9523Cache {
+    direction: [u8; 2],
+    output: [u8; 2],
+}
+
+/// Shared state for an I2C GPIO expander (AW9523-class, two 8-bit ports):
+/// cached direction and output registers so a pin's `set_as_output`/
+/// `set_high` only issues a write when a bit actually changes, and so
+/// sibling pins on the same port don't clobber each other's bits with a
+/// stale read-modify-write. `BidirectionalPin` is a synchronous trait (it's
+/// used for bit-banged SPI timing), so this uses the blocking
+/// `embedded_hal::i2c::I2c` rather than an async bus; wrap an async bus in a
+/// blocking adapter if that's all the board has.
+///
+/// The cache and the bus are two separate `RefCell`-backed locks (a
+/// `CriticalSectionRawMutex` for the cache, a `ThreadModeRawMutex` for the
+/// bus), but `set_bit` nests the cache lock entirely inside the bus lock for
+/// the whole read-modify-write-to-I2C sequence rather than releasing the bus
+/// lock in between, so two pins on the same port can never interleave a
+/// cache mutation with a stale hardware write. The input register (read by
+/// `is_high`) isn't cached — it reflects live physical state — so reads only
+/// take the bus lock for the duration of the I2C transaction.
+#[cfg(feature = "gpio-expander")]
+pub struct Aw9523Expander<I2C> {
+    i2c: embassy_sync::blocking_mutex::Mutex<
+        embassy_sync::blocking_mutex::raw::ThreadModeRawMutex,
+        core::cell::RefCell<I2C>,
+    >,
+    address: u8,
+    cache: embassy_sync::blocking_mutex::Mutex<
+        embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex,
+        core::cell::RefCell<Aw9523Cache>,
+    >,
+}
+
+#[cfg(feature = "gpio-expander")]
+impl<I2C> Aw9523Expander<I2C>
+where
+    I2C: embedded_hal::i2c::I2c,
+{
+    const REG_INPUT: [u8; 2] = [0x00, 0x01];
+    const REG_OUTPUT: [u8; 2] = [0x02, 0x03];
+    const REG_CONFIG: [u8; 2] = [0x04, 0x05];
+
+    pub fn new(i2c: I2C, address: u8) -> Self {
+        Self {
+            i2c: embassy_sync::blocking_mutex::Mutex::new(core::cell::RefCell::new(i2c)),
+            address,
+            cache: embassy_sync::blocking_mutex::Mutex::new(core::cell::RefCell::new(
+                Aw9523Cache {
+                    // Matches the expander's power-on-reset default: every pin an input.
+                    direction: [0xff, 0xff],
+                    output: [0x00, 0x00],
+                },
+            )),
+        }
+    }
+
+    fn read_reg(&self, reg: u8) -> Result<u8, I2C::Error> {
+        let mut value = [0u8];
+        self.i2c
+            .lock(|cell| cell.borrow_mut().write_read(self.address, &[reg], &mut value))?;
+        Ok(value[0])
+    }
+
+    /// Flips `bit` of `port` to `set` in the cache field selected by `field`
+    /// (direction or output) and, if that changed the register's value,
+    /// writes it to `reg_table[port]` over I2C — all under the bus's single
+    /// `ThreadModeRawMutex`. Holding that lock across the cache
+    /// read-modify-write *and* the I2C write (rather than releasing it in
+    /// between) is what keeps two pins on the same port from racing: the
+    /// cache mutation that decides what hardware should look like and the
+    /// write that makes it so can no longer be pried apart by another task's
+    /// write landing in between them.
+    fn set_bit(
+        &self,
+        reg_table: [u8; 2],
+        field: impl Fn(&mut Aw9523Cache) -> &mut [u8; 2],
+        port: usize,
+        bit: u8,
+        set: bool,
+    ) -> Result<(), I2C::Error> {
+        let mask = 1 << bit;
+        self.i2c.lock(|i2c_cell| {
+            let value = self.cache.lock(|cache_cell| {
+                let mut cache = cache_cell.borrow_mut();
+                let arr = field(&mut cache);
+                let currently_set = arr[port] & mask != 0;
+                if currently_set == set {
+                    None
+                } else {
+                    if set {
+                        arr[port] |= mask;
+                    } else {
+                        arr[port] &= !mask;
+                    }
+                    Some(arr[port])
+                }
+            });
+            match value {
+                Some(value) => i2c_cell
+                    .borrow_mut()
+                    .write(self.address, &[reg_table[port], value]),
+                None => Ok(()),
+            }
+        })
+    }
+}
+
+/// One pin on a shared [`Aw9523Expander`], identified by port (0 or 1) and
+/// bit index (0-7). Useful for moving the PMW3610's control lines (and
+/// matrix/reset/motion pins generally) off a pin-starved split half's native
+/// GPIOs.
+#[cfg(feature = "gpio-expander")]
+pub struct Aw9523Pin<'a, I2C> {
+    expander: &'a Aw9523Expander<I2C>,
+    port: usize,
+    bit: u8,
+}
+
+#[cfg(feature = "gpio-expander")]
+impl<'a, I2C> Aw9523Pin<'a, I2C> {
+    pub fn new(expander: &'a Aw9523Expander<I2C>, port: usize, bit: u8) -> Self {
+        Self {
+            expander,
+            port,
+            bit,
+        }
+    }
+}
+
+#[cfg(feature = "gpio-expander")]
+impl<'a, I2C> BidirectionalPin for Aw9523Pin<'a, I2C>
+where
+    I2C: embedded_hal::i2c::I2c,
+{
+    fn set_as_output(&mut self) {
+        let _ = self.expander.set_bit(
+            Aw9523Expander::<I2C>::REG_CONFIG,
+            |cache| &mut cache.direction,
+            self.port,
+            self.bit,
+            false,
+        );
+    }
+
+    fn set_as_input(&mut self) {
+        let _ = self.expander.set_bit(
+            Aw9523Expander::<I2C>::REG_CONFIG,
+            |cache| &mut cache.direction,
+            self.port,
+            self.bit,
+            true,
+        );
+    }
+
+    fn set_high(&mut self) {
+        let _ = self.expander.set_bit(
+            Aw9523Expander::<I2C>::REG_OUTPUT,
+            |cache| &mut cache.output,
+            self.port,
+            self.bit,
+            true,
+        );
+    }
+
+    fn set_low(&mut self) {
+        let _ = self.expander.set_bit(
+            Aw9523Expander::<I2C>::REG_OUTPUT,
+            |cache| &mut cache.output,
+            self.port,
+            self.bit,
+            false,
+        );
+    }
+
+    /// Unlike the other methods here, this always issues an I2C read: the
+    /// expander's input register reflects the live physical pin state and
+    /// isn't cached, so a driver that polls this in a tight loop (like the
+    /// sensor's motion-pin check) will pay an I2C transaction's latency on
+    /// every poll and should prefer a real interrupt line where possible.
+    /// That transaction runs under the bus's own `ThreadModeRawMutex`, not a
+    /// critical section, so it doesn't hold off other interrupts while it's
+    /// in flight.
+    fn is_high(&self) -> bool {
+        let mask = 1 << self.bit;
+        let reg = Aw9523Expander::<I2C>::REG_INPUT[self.port];
+        self.expander
+            .read_reg(reg)
+            .map(|value| value & mask != 0)
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+
+    /// Stand-in for `Pmw3610`'s `MOTION` type parameter: suspend/resume and
+    /// register access never touch it, so the tests below just need a
+    /// concrete `InputPin` to satisfy the bound.
+    struct NoPin;
+
+    impl embedded_hal::digital::ErrorType for NoPin {
+        type Error = core::convert::Infallible;
+    }
+
+    impl InputPin for NoPin {
+        fn is_high(&mut self) -> Result<bool, Self::Error> {
+            Ok(false)
+        }
+        fn is_low(&mut self) -> Result<bool, Self::Error> {
+            Ok(true)
+        }
+    }
+
+    /// Records every byte written and serves canned register reads in
+    /// order, the minimal fake [`Pmw3610Transport`] needs to drive
+    /// `Pmw3610`'s register access and suspend/resume logic without real
+    /// hardware.
+    struct MockTransport {
+        writes: Vec<u8>,
+        reads: Vec<u8>,
+    }
+
+    impl MockTransport {
+        fn new(reads: Vec<u8>) -> Self {
+            Self {
+                writes: Vec::new(),
+                reads,
+            }
+        }
+
+        /// True if `[addr, value]` appears as a contiguous pair in the
+        /// recorded write stream, i.e. a `write_reg(addr, value)` happened.
+        fn wrote(&self, addr: u8, value: u8) -> bool {
+            self.writes.windows(2).any(|pair| pair == [addr, value])
+        }
+    }
+
+    impl Pmw3610Transport for MockTransport {
+        async fn begin(&mut self) {}
+        async fn end(&mut self) {}
+        async fn idle(&mut self) {}
+
+        async fn write_byte(&mut self, byte: u8) {
+            self.writes.push(byte);
+        }
+
+        async fn read_byte(&mut self) -> u8 {
+            if self.reads.is_empty() {
+                0
+            } else {
+                self.reads.remove(0)
+            }
+        }
+    }
+
+    fn sensor(reads: Vec<u8>) -> Pmw3610<MockTransport, NoPin> {
+        Pmw3610::new(MockTransport::new(reads), None, Pmw3610Config::default())
+    }
+
+    #[test]
+    fn suspend_writes_shutdown_and_marks_suspended() {
+        let mut sensor = sensor(Vec::new());
+        assert_eq!(sensor.power_state(), PowerState::Active);
+
+        block_on(sensor.suspend()).unwrap();
+
+        assert_eq!(sensor.power_state(), PowerState::Suspended);
+        assert!(sensor.transport.wrote(PMW3610_SHUTDOWN | SPI_WRITE, SHUTDOWN_ENABLE));
+    }
+
+    #[test]
+    fn suspend_is_idempotent() {
+        let mut sensor = sensor(Vec::new());
+        block_on(sensor.suspend()).unwrap();
+        let writes_after_first = sensor.transport.writes.len();
+
+        block_on(sensor.suspend()).unwrap();
+
+        assert_eq!(sensor.transport.writes.len(), writes_after_first);
+    }
+
+    #[test]
+    fn resume_wakes_sensor_and_restores_config() {
+        let mut sensor = sensor(Vec::new());
+        block_on(sensor.suspend()).unwrap();
+
+        block_on(sensor.resume()).unwrap();
+
+        assert_eq!(sensor.power_state(), PowerState::Active);
+        assert!(sensor.transport.wrote(PMW3610_POWER_UP_RESET | SPI_WRITE, POWER_UP_WAKEUP));
+    }
+
+    #[test]
+    fn resume_without_prior_suspend_is_a_noop() {
+        let mut sensor = sensor(Vec::new());
+
+        block_on(sensor.resume()).unwrap();
+
+        assert_eq!(sensor.power_state(), PowerState::Active);
+        assert!(sensor.transport.writes.is_empty());
+    }
+
+    #[test]
+    fn set_resolution_round_trips_through_the_mock_transport() {
+        let mut sensor = sensor(Vec::from([0xff]));
+
+        block_on(sensor.set_resolution(400)).unwrap();
+
+        // 0xff with RES_STEP_RES_MASK cleared, then the 400 CPI / 200-step
+        // count (2) merged back in.
+        assert!(sensor.transport.wrote(PMW3610_RES_STEP | SPI_WRITE, 0xe2));
+    }
+}
@@ -0,0 +1,209 @@
+use defmt::unwrap;
+use embassy_futures::select::{select, Either};
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::channel::Channel;
+use embassy_time::{Duration, Timer};
+use embedded_hal::digital::InputPin;
+use rmk::ble::BleState;
+use rmk::channel::{ControllerSub, CONTROLLER_CHANNEL};
+use rmk::controller::Controller;
+use rmk::event::{Axis, AxisEvent, AxisValType, ControllerEvent, Event};
+use rmk::input_device::InputDevice;
+
+/// A MIDI message produced by a key or encoder action, independent of the USB
+/// vs BLE-MIDI transport that eventually carries it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MidiMessage {
+    NoteOn { channel: u8, note: u8, velocity: u8 },
+    NoteOff { channel: u8, note: u8, velocity: u8 },
+    ControlChange { channel: u8, controller: u8, value: u8 },
+}
+
+/// Queue of outgoing MIDI messages, fed by key/encoder processing and drained
+/// by [`MidiOutput`]. Kept separate from `CONTROLLER_CHANNEL` so existing HID
+/// keymaps are unaffected by boards that opt into MIDI.
+pub static MIDI_CHANNEL: Channel<CriticalSectionRawMutex, MidiMessage, 8> = Channel::new();
+
+/// An encoder rotation mapped to a relative MIDI Control Change, the control
+/// surface analogue of `encoder!(cw, ccw)` for continuous parameters.
+#[derive(Clone, Copy)]
+pub struct MidiEncoderAction {
+    pub channel: u8,
+    pub controller: u8,
+    /// Signed step applied to the running CC value per detent.
+    pub delta: i8,
+}
+
+/// Build one `MidiEncoderAction`; two of these in a `[cw, ccw]` array form
+/// the per-encoder pair, the MIDI counterpart of `encoder!(cw, ccw)`.
+#[macro_export]
+macro_rules! midi_cc {
+    ($channel:expr, $controller:expr, $delta:expr) => {
+        $crate::midi::MidiEncoderAction {
+            channel: $channel,
+            controller: $controller,
+            delta: $delta,
+        }
+    };
+}
+
+/// A key mapped to a MIDI Note On (on press) / Note Off (on release).
+#[derive(Clone, Copy)]
+pub struct MidiNoteAction {
+    pub channel: u8,
+    pub note: u8,
+    pub velocity: u8,
+}
+
+#[macro_export]
+macro_rules! midi_note {
+    ($channel:expr, $note:expr, $velocity:expr) => {
+        $crate::midi::MidiNoteAction {
+            channel: $channel,
+            note: $note,
+            velocity: $velocity,
+        }
+    };
+}
+
+/// Polls a bank of dedicated switches as MIDI notes, independent of the main
+/// key matrix, the same way `RotaryEncoderDevice` sits alongside it for
+/// continuous input: each pin presses to a `NoteOn` and releases to the
+/// matching `NoteOff` on its own [`MidiNoteAction`], pushed onto
+/// [`MIDI_CHANNEL`] rather than `KEYBOARD_REPORT_CHANNEL`.
+pub struct MidiKeyDevice<P, const N: usize> {
+    pins: [P; N],
+    notes: [MidiNoteAction; N],
+    pressed: [bool; N],
+    poll_interval: Duration,
+}
+
+impl<P, const N: usize> MidiKeyDevice<P, N>
+where
+    P: InputPin,
+{
+    pub fn new(pins: [P; N], notes: [MidiNoteAction; N], poll_interval: Duration) -> Self {
+        Self {
+            pins,
+            notes,
+            pressed: [false; N],
+            poll_interval,
+        }
+    }
+
+    /// No axis motion of its own; `Event::Joystick` is returned purely so the
+    /// note is visible to the processor chain, the same compatibility reason
+    /// `RotaryEncoderDevice::zero_axes` returns one.
+    fn zero_axes() -> Event {
+        Event::Joystick([
+            AxisEvent { typ: AxisValType::Rel, axis: Axis::X, value: 0 },
+            AxisEvent { typ: AxisValType::Rel, axis: Axis::Y, value: 0 },
+            AxisEvent { typ: AxisValType::Rel, axis: Axis::Z, value: 0 },
+        ])
+    }
+}
+
+impl<P, const N: usize> InputDevice for MidiKeyDevice<P, N>
+where
+    P: InputPin,
+{
+    async fn read_event(&mut self) -> Event {
+        loop {
+            Timer::after(self.poll_interval).await;
+
+            for i in 0..N {
+                let is_pressed = self.pins[i].is_low().unwrap_or(false);
+                if is_pressed == self.pressed[i] {
+                    continue;
+                }
+                self.pressed[i] = is_pressed;
+
+                let note = self.notes[i];
+                let message = if is_pressed {
+                    MidiMessage::NoteOn {
+                        channel: note.channel,
+                        note: note.note,
+                        velocity: note.velocity,
+                    }
+                } else {
+                    MidiMessage::NoteOff {
+                        channel: note.channel,
+                        note: note.note,
+                        velocity: note.velocity,
+                    }
+                };
+                MIDI_CHANNEL.send(message).await;
+                return Self::zero_axes();
+            }
+        }
+    }
+}
+
+/// Encodes a MIDI message into a 3-byte USB-MIDI / BLE-MIDI event payload
+/// (status byte, data1, data2), shared by both transports.
+fn encode(message: MidiMessage) -> [u8; 3] {
+    match message {
+        MidiMessage::NoteOn { channel, note, velocity } => [0x90 | (channel & 0x0f), note, velocity],
+        MidiMessage::NoteOff { channel, note, velocity } => [0x80 | (channel & 0x0f), note, velocity],
+        MidiMessage::ControlChange { channel, controller, value } => {
+            [0xb0 | (channel & 0x0f), controller, value]
+        }
+    }
+}
+
+/// Drains [`MIDI_CHANNEL`] and forwards each message over USB-MIDI, or over
+/// BLE-MIDI when the keyboard is running wireless. `transport` abstracts the
+/// underlying class driver the same way `Pmw3610Transport` abstracts the
+/// sensor's SPI link, so a board wires one concrete sender without this
+/// controller caring which link is active.
+pub struct MidiOutput<T: MidiSender> {
+    transport: T,
+    sub: ControllerSub,
+    wireless: bool,
+}
+
+/// Sink for an encoded 3-byte MIDI event, implemented once per transport
+/// (USB-MIDI class, BLE-MIDI service). `wireless` is the link `MidiOutput`
+/// last observed from `ControllerEvent::BleState`, so an implementation that
+/// actually speaks both wires (e.g. a combo USB/BLE-MIDI sender) knows
+/// whether to send this payload as a USB-MIDI class report or re-wrap it as
+/// a BLE-MIDI characteristic write; a single-link implementation can ignore
+/// it.
+pub trait MidiSender {
+    async fn send(&mut self, payload: [u8; 3], wireless: bool);
+}
+
+impl<T: MidiSender> MidiOutput<T> {
+    pub fn new(transport: T) -> Self {
+        Self {
+            transport,
+            sub: unwrap!(CONTROLLER_CHANNEL.subscriber()),
+            wireless: false,
+        }
+    }
+}
+
+impl<T: MidiSender> Controller for MidiOutput<T> {
+    type Event = ControllerEvent;
+
+    async fn process_event(&mut self, event: Self::Event) {
+        // Track the active link so a board-level BLE-MIDI bridge knows
+        // whether to re-wrap outgoing packets as BLE-MIDI characteristic
+        // writes; USB-MIDI framing is handled by `transport` either way.
+        if let ControllerEvent::BleState(_, state) = event {
+            self.wireless = matches!(state, BleState::Connected);
+        }
+    }
+
+    /// Races the MIDI queue against the controller channel so a queued note
+    /// or CC message is forwarded as soon as it's produced, without waiting
+    /// on a BLE state change to drive the loop.
+    async fn next_message(&mut self) -> Self::Event {
+        loop {
+            match select(self.sub.next_message_pure(), MIDI_CHANNEL.receive()).await {
+                Either::First(event) => return event,
+                Either::Second(message) => self.transport.send(encode(message), self.wireless).await,
+            }
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use defmt::{info, unwrap};
+use embassy_futures::select::{select, Either};
+use embassy_time::{Duration, Timer};
+use embedded_hal::spi::SpiBus;
+use rmk::ble::BleState;
+use rmk::channel::{ControllerSub, CONTROLLER_CHANNEL};
+use rmk::controller::Controller;
+use rmk::event::ControllerEvent;
+use smart_leds::{SmartLedsWrite, RGB8};
+use ws2812_spi::Ws2812;
+
+use crate::keymap::NUM_LAYER;
+use crate::layer_led::LayerStyle;
+
+/// Number of WS2812 LEDs in the underglow chain.
+pub const NUM_LEDS: usize = 10;
+
+const FRAME_INTERVAL_MS: u64 = 20;
+
+/// Underglow animation effect.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum RgbEffect {
+    Solid(RGB8),
+    Breathing(RGB8),
+    ColorWheel,
+}
+
+/// Drives a chain of WS2812 underglow LEDs over SPI, reacting to keyboard state.
+///
+/// Mirrors rumcake's backlight driver split: a board supplies its SPI bus and
+/// picks an effect, and this struct owns the framebuffer and animation engine.
+pub struct RgbController<SPI> {
+    driver: Ws2812<SPI>,
+    pixels: [RGB8; NUM_LEDS],
+    brightness: u8,
+    effect: RgbEffect,
+    step: u16,
+    sub: ControllerSub,
+    /// Per-layer color, shared with [`crate::layer_led::LayerIndicator`] so
+    /// the same `LayerStyle` table drives both the underglow and the
+    /// blue/red blink-count indicators; non-`Color` entries are ignored
+    /// here since those layers are indicated by `LayerIndicator` instead.
+    layer_styles: [LayerStyle; NUM_LAYER],
+}
+
+impl<SPI> RgbController<SPI>
+where
+    SPI: SpiBus,
+{
+    pub fn new(spi: SPI, layer_styles: [LayerStyle; NUM_LAYER]) -> Self {
+        Self {
+            driver: Ws2812::new(spi),
+            pixels: [RGB8::default(); NUM_LEDS],
+            brightness: 64,
+            effect: RgbEffect::Solid(RGB8::new(0, 0, 32)),
+            step: 0,
+            sub: unwrap!(CONTROLLER_CHANNEL.subscriber()),
+            layer_styles,
+        }
+    }
+
+    /// Global brightness scale applied to every pixel, 0-255.
+    pub fn brightness(&self) -> u8 {
+        self.brightness
+    }
+
+    pub fn set_brightness(&mut self, brightness: u8) {
+        self.brightness = brightness;
+    }
+
+    fn scale(&self, color: RGB8) -> RGB8 {
+        let b = self.brightness as u16;
+        RGB8::new(
+            ((color.r as u16 * b) / 255) as u8,
+            ((color.g as u16 * b) / 255) as u8,
+            ((color.b as u16 * b) / 255) as u8,
+        )
+    }
+
+    fn step_animation(&mut self) {
+        self.step = self.step.wrapping_add(1);
+        let frame = match self.effect {
+            RgbEffect::Solid(c) => c,
+            RgbEffect::Breathing(c) => {
+                let phase = (self.step % 128) as i32;
+                let level = if phase < 64 { phase } else { 128 - phase };
+                RGB8::new(
+                    ((c.r as i32 * level) / 64) as u8,
+                    ((c.g as i32 * level) / 64) as u8,
+                    ((c.b as i32 * level) / 64) as u8,
+                )
+            }
+            RgbEffect::ColorWheel => wheel(((self.step / 2) % 256) as u8),
+        };
+        let scaled = self.scale(frame);
+        self.pixels = [scaled; NUM_LEDS];
+    }
+
+    async fn flush(&mut self) {
+        if self.driver.write(self.pixels.iter().cloned()).is_err() {
+            info!("RGB: failed to flush underglow frame");
+        }
+    }
+}
+
+impl<SPI> Controller for RgbController<SPI>
+where
+    SPI: SpiBus,
+{
+    type Event = ControllerEvent;
+
+    async fn process_event(&mut self, event: Self::Event) {
+        match event {
+            ControllerEvent::BleState(_, BleState::Connected) => {
+                self.effect = RgbEffect::Solid(RGB8::new(0, 32, 0));
+            }
+            ControllerEvent::BleState(_, BleState::Advertising) => {
+                self.effect = RgbEffect::Breathing(RGB8::new(0, 0, 32));
+            }
+            ControllerEvent::BleState(_, BleState::None) => {
+                self.effect = RgbEffect::Breathing(RGB8::new(32, 0, 0));
+            }
+            ControllerEvent::SplitCentral(true) => {
+                self.effect = RgbEffect::ColorWheel;
+            }
+            ControllerEvent::SplitCentral(false) => {
+                self.effect = RgbEffect::Breathing(RGB8::new(32, 0, 0));
+            }
+            ControllerEvent::Layer(layer) => {
+                if let Some(LayerStyle::Color(color)) = self.layer_styles.get(layer as usize).copied() {
+                    self.effect = RgbEffect::Solid(color);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Same non-blocking pattern as `SplitConnectionLed`/`LayerIndicator`: the
+    /// frame timer races the event channel, so the animation advances one
+    /// frame at a time between events instead of needing a second task that
+    /// would have to share ownership of `self`.
+    async fn next_message(&mut self) -> Self::Event {
+        loop {
+            match select(
+                self.sub.next_message_pure(),
+                Timer::after(Duration::from_millis(FRAME_INTERVAL_MS)),
+            )
+            .await
+            {
+                Either::First(event) => return event,
+                Either::Second(_) => {
+                    self.step_animation();
+                    self.flush().await;
+                }
+            }
+        }
+    }
+}
+
+/// Standard RGB color wheel, 0-255 maps to a full hue rotation.
+fn wheel(pos: u8) -> RGB8 {
+    if pos < 85 {
+        RGB8::new(255 - pos * 3, pos * 3, 0)
+    } else if pos < 170 {
+        let pos = pos - 85;
+        RGB8::new(0, 255 - pos * 3, pos * 3)
+    } else {
+        let pos = pos - 170;
+        RGB8::new(pos * 3, 0, 255 - pos * 3)
+    }
+}
@@ -0,0 +1,225 @@
+use defmt::unwrap;
+use embassy_futures::select::{select, Either};
+use embassy_time::{Duration, Timer};
+use embedded_hal::digital::InputPin;
+use rmk::channel::{ControllerSub, CONTROLLER_CHANNEL, KEYBOARD_REPORT_CHANNEL};
+use rmk::event::{Axis, AxisEvent, AxisValType, ControllerEvent, Event};
+use rmk::hid::Report;
+use rmk::input_device::InputDevice;
+use usbd_hid::descriptor::{KeyboardReport, MouseReport};
+
+use crate::keymap::NUM_LAYER;
+use crate::midi::{MidiEncoderAction, MidiMessage, MIDI_CHANNEL};
+
+/// Full-step quadrature transition table, indexed by `(prev_state << 2) | new_state`
+/// where each 2-bit state packs the encoder's A/B pins as `(a << 1) | b`. Valid
+/// single steps resolve to `1`/`-1`; a missed step, bounce, or repeated state
+/// resolves to `0` so contact noise can't produce a spurious detent.
+#[rustfmt::skip]
+const TRANSITION_TABLE: [i8; 16] = [
+    0,  1, -1,  0,
+   -1,  0,  0,  1,
+    1,  0,  0, -1,
+    0, -1,  1,  0,
+];
+
+/// Quadrature sub-steps per detent for a typical full-step (4x) encoder.
+const SUB_STEPS_PER_DETENT: i8 = 4;
+
+/// What a detent of rotation does downstream.
+#[derive(Clone, Copy)]
+pub enum EncoderMapping {
+    /// Send a scroll wheel tick via `MouseReport::wheel`, clockwise positive.
+    ScrollWheel,
+    /// Tap the given HID keycode: `cw` on clockwise, `ccw` on counter-clockwise.
+    Key { cw: u8, ccw: u8 },
+}
+
+/// Decodes a 2-pin incremental (quadrature) rotary encoder as an `InputDevice`,
+/// a sibling input path to the PMW3610 optical sensor and MIDI controllers.
+/// Polls both pins, advances the classic full-step Gray-code state machine,
+/// and accumulates sub-steps until a full detent has turned before acting.
+pub struct RotaryEncoderDevice<A, B> {
+    pin_a: A,
+    pin_b: B,
+    mapping: EncoderMapping,
+    poll_interval: Duration,
+    prev_state: u8,
+    sub_steps: i8,
+    /// Keycode to release on the next poll, for `EncoderMapping::Key` taps.
+    pending_release: Option<u8>,
+    /// This encoder's slice of `get_default_midi_encoder_overrides()`, one
+    /// entry per layer: when the active layer's entry is `Some`, rotation
+    /// sends a Control Change on `MIDI_CHANNEL` instead of following
+    /// `mapping`.
+    midi_overrides: [Option<[MidiEncoderAction; 2]>; NUM_LAYER],
+    /// Running 7-bit CC value the MIDI override's `delta` is applied to,
+    /// clamped to the valid 0-127 range.
+    midi_cc_value: u8,
+    current_layer: u8,
+    sub: ControllerSub,
+}
+
+impl<A, B> RotaryEncoderDevice<A, B>
+where
+    A: InputPin,
+    B: InputPin,
+{
+    pub fn new(
+        pin_a: A,
+        pin_b: B,
+        mapping: EncoderMapping,
+        midi_overrides: [Option<[MidiEncoderAction; 2]>; NUM_LAYER],
+        poll_interval: Duration,
+    ) -> Self {
+        Self {
+            pin_a,
+            pin_b,
+            mapping,
+            poll_interval,
+            prev_state: 0,
+            sub_steps: 0,
+            pending_release: None,
+            midi_overrides,
+            midi_cc_value: 0,
+            current_layer: 0,
+            sub: unwrap!(CONTROLLER_CHANNEL.subscriber()),
+        }
+    }
+
+    fn read_state(&mut self) -> u8 {
+        let a = self.pin_a.is_high().unwrap_or(false) as u8;
+        let b = self.pin_b.is_high().unwrap_or(false) as u8;
+        (a << 1) | b
+    }
+
+    async fn send_key(keycode: u8) {
+        KEYBOARD_REPORT_CHANNEL
+            .send(Report::KeyboardReport(KeyboardReport {
+                modifier: 0,
+                reserved: 0,
+                leds: 0,
+                keycodes: [keycode, 0, 0, 0, 0, 0],
+            }))
+            .await;
+    }
+
+    async fn release_key() {
+        KEYBOARD_REPORT_CHANNEL
+            .send(Report::KeyboardReport(KeyboardReport {
+                modifier: 0,
+                reserved: 0,
+                leds: 0,
+                keycodes: [0; 6],
+            }))
+            .await;
+    }
+
+    /// No axis motion of its own; `Event::Joystick` is returned purely so the
+    /// detent is visible to the processor chain, the same compatibility
+    /// reason the PMW3610 path returns one.
+    fn zero_axes() -> Event {
+        Event::Joystick([
+            AxisEvent {
+                typ: AxisValType::Rel,
+                axis: Axis::X,
+                value: 0,
+            },
+            AxisEvent {
+                typ: AxisValType::Rel,
+                axis: Axis::Y,
+                value: 0,
+            },
+            AxisEvent {
+                typ: AxisValType::Rel,
+                axis: Axis::Z,
+                value: 0,
+            },
+        ])
+    }
+}
+
+impl<A, B> InputDevice for RotaryEncoderDevice<A, B>
+where
+    A: InputPin,
+    B: InputPin,
+{
+    async fn read_event(&mut self) -> Event {
+        loop {
+            if let Some(keycode) = self.pending_release.take() {
+                let _ = keycode;
+                Self::release_key().await;
+            }
+
+            // Race the poll tick against the controller channel so a layer
+            // change is picked up immediately instead of waiting for the
+            // next detent, the same non-blocking select idiom the PMW3610
+            // path uses against its motion pin.
+            match select(Timer::after(self.poll_interval), self.sub.next_message_pure()).await {
+                Either::First(()) => {}
+                Either::Second(ControllerEvent::Layer(layer)) => {
+                    self.current_layer = layer;
+                    continue;
+                }
+                Either::Second(_) => continue,
+            }
+
+            let new_state = self.read_state();
+            let index = ((self.prev_state << 2) | new_state) as usize;
+            self.prev_state = new_state;
+
+            let step = TRANSITION_TABLE[index];
+            if step == 0 {
+                continue;
+            }
+
+            self.sub_steps += step;
+            if self.sub_steps.unsigned_abs() < SUB_STEPS_PER_DETENT as u8 {
+                continue;
+            }
+            let clockwise = self.sub_steps > 0;
+            self.sub_steps = 0;
+
+            let midi_override = self
+                .midi_overrides
+                .get(self.current_layer as usize)
+                .copied()
+                .flatten();
+
+            match midi_override {
+                Some([cw_action, ccw_action]) => {
+                    let action = if clockwise { cw_action } else { ccw_action };
+                    self.midi_cc_value = self.midi_cc_value.saturating_add_signed(action.delta).min(127);
+                    MIDI_CHANNEL
+                        .send(MidiMessage::ControlChange {
+                            channel: action.channel,
+                            controller: action.controller,
+                            value: self.midi_cc_value,
+                        })
+                        .await;
+                }
+                None => match self.mapping {
+                    EncoderMapping::ScrollWheel => {
+                        let wheel: i8 = if clockwise { 1 } else { -1 };
+                        KEYBOARD_REPORT_CHANNEL
+                            .send(Report::MouseReport(MouseReport {
+                                buttons: 0,
+                                x: 0,
+                                y: 0,
+                                wheel,
+                                pan: 0,
+                            }))
+                            .await;
+                    }
+                    EncoderMapping::Key { cw, ccw } => {
+                        let keycode = if clockwise { cw } else { ccw };
+                        Self::send_key(keycode).await;
+                        self.pending_release = Some(keycode);
+                    }
+                },
+            }
+
+            return Self::zero_axes();
+        }
+    }
+}